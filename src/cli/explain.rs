@@ -0,0 +1,221 @@
+use crate::core::mock::Mock;
+use crate::matcher::any;
+use crate::matcher::http_version::parse_version;
+use crate::model::mapping::files::load_mappings;
+use crate::model::request::{MockRegistrable, RequestPatternStub};
+use crate::net::encoding::decode_body;
+use crate::net::request::{BodyStatus, Request, DEFAULT_MAX_BODY_SIZE};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Version};
+use std::path::Path;
+use std::process::ExitCode;
+
+pub fn explain(mappings: &Path, request: &Path) -> ExitCode {
+    let request = match std::fs::read(request)
+        .map_err(|e| format!("Cannot read {} : {}", request.display(), e))
+        .and_then(|raw| parse_request(&raw))
+    {
+        Ok(request) => request,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let loaded = match load_mappings(mappings) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("Cannot read mappings from {} : {}", mappings.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{} {}", request.method, request.url);
+    println!();
+
+    let mut selected = None;
+    for loaded in loaded.iter() {
+        let mapping = match loaded.mapping.as_ref() {
+            Ok(mapping) => mapping,
+            Err(errors) => {
+                println!("! {} is invalid and would not be loaded", loaded.location());
+                for error in errors {
+                    println!("    {}", error);
+                }
+                continue;
+            }
+        };
+
+        let mismatches = mismatches(&mapping.request, &request);
+        if mismatches.is_empty() {
+            println!("+ {} matches", loaded.location());
+            // The most recently registered stub wins, and files are registered in order
+            selected = Some(loaded.location());
+        } else {
+            println!("- {} does not match", loaded.location());
+            for (attribute, expected) in mismatches {
+                println!("    {:<14}{}", attribute, expected);
+            }
+        }
+    }
+
+    println!();
+    match selected {
+        Some(selected) => println!("Answered by {}", selected),
+        None => println!("No stub matches, the server would answer 404"),
+    }
+    ExitCode::SUCCESS
+}
+
+fn mismatches(pattern: &RequestPatternStub, request: &Request) -> Vec<(&'static str, String)> {
+    let attributes = [
+        (
+            "method",
+            RequestPatternStub {
+                method: pattern.method.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "path",
+            RequestPatternStub {
+                path: pattern.path.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "queryParams",
+            RequestPatternStub {
+                query: pattern.query.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "headers",
+            RequestPatternStub {
+                headers: pattern.headers.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "bodyPatterns",
+            RequestPatternStub {
+                body: pattern.body.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "clientCertificate",
+            RequestPatternStub {
+                client_certificate: pattern.client_certificate.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "clientIp",
+            RequestPatternStub {
+                client_ip: pattern.client_ip.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "httpVersion",
+            RequestPatternStub {
+                http_version: pattern.http_version.clone(),
+                ..Default::default()
+            },
+        ),
+        (
+            "listener",
+            RequestPatternStub {
+                listener: pattern.listener.clone(),
+                ..Default::default()
+            },
+        ),
+    ];
+
+    attributes
+        .into_iter()
+        .filter(|(_, attribute)| *attribute != RequestPatternStub::default())
+        .filter(|(_, attribute)| {
+            !attribute
+                .register(Mock::stub_for(any()))
+                .build()
+                .matches(request)
+        })
+        .map(|(name, attribute)| {
+            let expected = serde_json::to_string(&attribute).unwrap_or_default();
+            (name, expected)
+        })
+        .collect()
+}
+
+fn parse_request(raw: &[u8]) -> Result<Request, String> {
+    let (head, raw_body) = match find(raw, b"\r\n\r\n") {
+        Some(end) => (&raw[..end], &raw[end + 4..]),
+        None => match find(raw, b"\n\n") {
+            Some(end) => (&raw[..end], &raw[end + 2..]),
+            None => (raw, &raw[raw.len()..]),
+        },
+    };
+    let head =
+        std::str::from_utf8(head).map_err(|e| format!("Request head is not UTF-8 : {}", e))?;
+    let mut lines = head.lines();
+
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(format!("Invalid request line : {}", request_line));
+    };
+    let method = Method::from_bytes(method.as_bytes())
+        .map_err(|e| format!("Invalid method {} : {}", method, e))?;
+    let version = match parts.next() {
+        Some(version) => parse_version(version).map_err(|e| e.message())?,
+        None => Version::HTTP_11,
+    };
+
+    let mut headers = HeaderMap::new();
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid header line : {}", line))?;
+        let name = HeaderName::try_from(name.trim())
+            .map_err(|e| format!("Invalid header name {} : {}", name, e))?;
+        let value = HeaderValue::try_from(value.trim())
+            .map_err(|e| format!("Invalid header value {} : {}", value, e))?;
+        headers.append(name, value);
+    }
+
+    let url = if target.starts_with('/') {
+        format!("http://localhost{}", target)
+    } else {
+        target.to_string()
+    };
+    let url = url
+        .parse()
+        .map_err(|e| format!("Invalid request target {} : {}", target, e))?;
+
+    let raw_body = raw_body.to_vec();
+    let body = decode_body(&headers, &raw_body, DEFAULT_MAX_BODY_SIZE)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| raw_body.clone());
+
+    Ok(Request {
+        url,
+        method,
+        version,
+        headers,
+        body,
+        raw_body,
+        client_certificate: None,
+        body_status: BodyStatus::Complete,
+        remote_address: None,
+        client_ip: None,
+        listener: None,
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
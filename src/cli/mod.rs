@@ -0,0 +1,296 @@
+use crate::core::journal::JournalSettings;
+use crate::net::request::{RequestBodySettings, DEFAULT_MAX_BODY_SIZE};
+use crate::response::proxy::ProxyConfig;
+use crate::server::config::ServerConfig;
+use crate::server::listener::Endpoint;
+use crate::server::protocol::{HttpProtocols, ProtocolSettings};
+use crate::server::recording::RecordSpec;
+use crate::server::tls::{ClientAuth, TlsCertificate};
+use clap::{Args, Parser, Subcommand};
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::time::Duration;
+use url::Url;
+
+pub mod explain;
+pub mod transfer;
+pub mod validate;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = env!("CARGO_PKG_NAME"),
+    version,
+    about = "Standalone HTTP mock server",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Options of `serve`, which runs when no subcommand is given.
+    #[command(flatten)]
+    pub serve: ServeArgs,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the mock server.
+    Serve(Box<ServeArgs>),
+    /// Check every mapping file under a directory and report invalid fields.
+    Validate {
+        /// Directory holding the mapping files.
+        dir: PathBuf,
+    },
+    /// Split a `{"mappings": [...]}` file into one mapping file per stub.
+    Import {
+        /// File to import, holding one mapping or a `mappings` array.
+        file: PathBuf,
+        /// Directory whose `mappings/` receives the files.
+        #[arg(long, default_value = ".")]
+        root_dir: PathBuf,
+    },
+    /// Bundle every mapping file into a single `{"mappings": [...]}` document.
+    Export {
+        /// Directory whose `mappings/` is exported.
+        #[arg(long, default_value = ".")]
+        root_dir: PathBuf,
+        /// Write to this file instead of the standard output.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Show which stub would answer a request, and why the others would not.
+    Explain {
+        /// Directory holding the mapping files.
+        #[arg(long)]
+        mappings: PathBuf,
+        /// File holding a raw HTTP request: request line, headers, blank line and body.
+        #[arg(long)]
+        request: PathBuf,
+    },
+}
+
+/// Every option can also be set through the environment variable named next to it.
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Port of the HTTP listener.
+    #[arg(long, env = "SPOOF_PORT", default_value_t = 3000)]
+    pub port: u16,
+
+    /// Address to listen on, IPv4 or IPv6.
+    #[arg(long, env = "SPOOF_BIND_ADDRESS", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub bind_address: IpAddr,
+
+    /// Directory holding `mappings/` and `__files/`.
+    #[arg(long, env = "SPOOF_ROOT_DIR", default_value = ".")]
+    pub root_dir: PathBuf,
+
+    /// Log at debug level.
+    #[arg(long, env = "SPOOF_VERBOSE")]
+    pub verbose: bool,
+
+    /// Delay in milliseconds added to every stubbed response.
+    #[arg(long, env = "SPOOF_GLOBAL_RESPONSE_DELAY", value_name = "MILLISECONDS")]
+    pub global_response_delay: Option<u64>,
+
+    /// Do not keep received requests.
+    #[arg(long, env = "SPOOF_NO_REQUEST_JOURNAL")]
+    pub no_request_journal: bool,
+
+    /// Keep at most this many received requests, dropping the oldest.
+    #[arg(long, env = "SPOOF_MAX_REQUEST_JOURNAL_ENTRIES")]
+    pub max_request_journal_entries: Option<usize>,
+
+    /// Largest request body accepted, in bytes. Larger ones are answered with 413.
+    #[arg(long, env = "SPOOF_MAX_REQUEST_BODY_SIZE", default_value_t = DEFAULT_MAX_BODY_SIZE)]
+    pub max_request_body_size: usize,
+
+    /// Accept request bodies over the maximum size, keeping only their size and SHA-256.
+    #[arg(long, env = "SPOOF_DIGEST_LARGE_REQUEST_BODIES")]
+    pub digest_large_request_bodies: bool,
+
+    /// Take the client address from `Forwarded` or `X-Forwarded-For`, for a server behind a proxy.
+    #[arg(long, env = "SPOOF_TRUST_FORWARDED_HEADERS")]
+    pub trust_forwarded_headers: bool,
+
+    /// Only speak HTTP/1.1, HTTPS offers no HTTP/2 either.
+    #[arg(long, env = "SPOOF_HTTP1_ONLY", conflicts_with = "http2_only")]
+    pub http1_only: bool,
+
+    /// Only speak HTTP/2, over plain HTTP through prior knowledge (h2c).
+    #[arg(long, env = "SPOOF_HTTP2_ONLY")]
+    pub http2_only: bool,
+
+    /// Close HTTP/1 connections whose request headers take longer than this to arrive.
+    #[arg(long, env = "SPOOF_HEADER_READ_TIMEOUT", value_name = "MILLISECONDS")]
+    pub header_read_timeout: Option<u64>,
+
+    /// Close connections after the first response.
+    #[arg(long, env = "SPOOF_DISABLE_KEEP_ALIVE")]
+    pub disable_keep_alive: bool,
+
+    /// Close connections without a request in flight for this long.
+    #[arg(long, env = "SPOOF_IDLE_TIMEOUT", value_name = "MILLISECONDS")]
+    pub idle_timeout: Option<u64>,
+
+    /// Maximum number of concurrent streams per HTTP/2 connection.
+    #[arg(long, env = "SPOOF_MAX_CONCURRENT_STREAMS")]
+    pub max_concurrent_streams: Option<u32>,
+
+    /// Largest request head accepted, in bytes. HTTP/1 never goes under 8192.
+    #[arg(long, env = "SPOOF_MAX_HEADER_SIZE", value_name = "BYTES")]
+    pub max_header_size: Option<usize>,
+
+    /// Port of the HTTPS listener.
+    #[arg(long, env = "SPOOF_HTTPS_PORT")]
+    pub https_port: Option<u16>,
+
+    /// More endpoints to serve the same stubs on: http://HOST:PORT, https://HOST:PORT or
+    /// unix:PATH, each optionally tagged with #TAG for stubs to match on.
+    #[arg(
+        long = "listen",
+        env = "SPOOF_LISTEN",
+        value_name = "ENDPOINT",
+        value_delimiter = ','
+    )]
+    pub listeners: Vec<Endpoint>,
+
+    /// Do not serve plain HTTP on --port, only HTTPS and the other listeners.
+    #[arg(long, env = "SPOOF_DISABLE_HTTP")]
+    pub disable_http: bool,
+
+    /// PEM certificate chain of the HTTPS listener. A certificate is generated when omitted.
+    #[arg(long, env = "SPOOF_HTTPS_CERTIFICATE", requires = "https_private_key")]
+    pub https_certificate: Option<PathBuf>,
+
+    /// PEM private key matching `--https-certificate`.
+    #[arg(long, env = "SPOOF_HTTPS_PRIVATE_KEY", requires = "https_certificate")]
+    pub https_private_key: Option<PathBuf>,
+
+    /// PEM bundle of the CAs trusted to sign client certificates. Enables mutual TLS.
+    #[arg(
+        long,
+        env = "SPOOF_HTTPS_TRUSTED_CERTIFICATES",
+        requires = "https_port"
+    )]
+    pub https_trusted_certificates: Option<PathBuf>,
+
+    /// Reject HTTPS clients that present no certificate, instead of only asking for one.
+    #[arg(
+        long,
+        env = "SPOOF_HTTPS_REQUIRE_CLIENT_CERTIFICATE",
+        requires = "https_trusted_certificates"
+    )]
+    pub https_require_client_certificate: bool,
+
+    /// DNS names and IP addresses of the generated certificate.
+    #[arg(
+        long,
+        env = "SPOOF_HTTPS_SUBJECT_ALT_NAMES",
+        value_delimiter = ',',
+        default_value = "localhost,127.0.0.1,::1",
+        conflicts_with = "https_certificate"
+    )]
+    pub https_subject_alt_names: Vec<String>,
+
+    /// Proxy requests that match no stub to this base URL.
+    #[arg(long, env = "SPOOF_PROXY_ALL", value_name = "URL", value_parser = Url::parse)]
+    pub proxy_all: Option<Url>,
+
+    /// Record proxied requests and write them as mappings on shutdown.
+    #[arg(long, env = "SPOOF_RECORD_MAPPINGS", requires = "proxy_all")]
+    pub record_mappings: bool,
+
+    /// Reload the mapping files when `mappings/` or `__files/` change. SIGHUP always reloads them.
+    #[arg(long, env = "SPOOF_WATCH")]
+    pub watch: bool,
+
+    /// Do not print the startup banner.
+    #[arg(long, env = "SPOOF_DISABLE_BANNER")]
+    pub disable_banner: bool,
+}
+
+impl ServeArgs {
+    pub fn log_level(&self) -> &'static str {
+        if self.verbose {
+            "debug"
+        } else {
+            "info"
+        }
+    }
+
+    pub fn server_config(&self) -> ServerConfig {
+        let mut config = ServerConfig::new(self.port, false)
+            .with_bind_address(self.bind_address)
+            .with_root_dir(&self.root_dir)
+            .with_journal(JournalSettings {
+                enabled: !self.no_request_journal,
+                max_entries: self.max_request_journal_entries,
+            })
+            .with_request_body(RequestBodySettings {
+                max_size: self.max_request_body_size,
+                digest_oversized: self.digest_large_request_bodies,
+            })
+            .with_protocol(ProtocolSettings {
+                protocols: match (self.http1_only, self.http2_only) {
+                    (true, _) => HttpProtocols::Http1Only,
+                    (_, true) => HttpProtocols::Http2Only,
+                    _ => HttpProtocols::Auto,
+                },
+                header_read_timeout: self.header_read_timeout.map(Duration::from_millis),
+                keep_alive: !self.disable_keep_alive,
+                idle_timeout: self.idle_timeout.map(Duration::from_millis),
+                max_concurrent_streams: self.max_concurrent_streams,
+                max_header_size: self.max_header_size,
+            });
+
+        if let Some(delay) = self.global_response_delay {
+            config = config.with_global_response_delay(Duration::from_millis(delay));
+        }
+        if let Some(https_port) = self.https_port {
+            config = config.with_https_port(https_port);
+        }
+        if let Some(trusted_certificates) = self.https_trusted_certificates.as_ref() {
+            config = config.with_client_auth(ClientAuth {
+                trusted_certificates: trusted_certificates.clone(),
+                required: self.https_require_client_certificate,
+            });
+        }
+        for endpoint in &self.listeners {
+            config = config.with_listener(endpoint.clone());
+        }
+        if self.disable_http {
+            config = config.without_http();
+        }
+        config = config.with_tls_certificate(
+            match (
+                self.https_certificate.as_ref(),
+                self.https_private_key.as_ref(),
+            ) {
+                (Some(certificate), Some(private_key)) => TlsCertificate::Pem {
+                    certificate: certificate.clone(),
+                    private_key: private_key.clone(),
+                },
+                _ => TlsCertificate::SelfSigned {
+                    subject_alt_names: self.https_subject_alt_names.clone(),
+                },
+            },
+        );
+
+        if self.trust_forwarded_headers {
+            config = config.with_forwarded_headers();
+        }
+        if self.watch {
+            config = config.with_file_watching();
+        }
+
+        if self.record_mappings {
+            config = config.with_recording(RecordSpec {
+                target_base_url: self.proxy_all.as_ref().map(Url::to_string),
+                ..Default::default()
+            });
+        } else if let Some(proxy_all) = self.proxy_all.as_ref() {
+            config = config.with_fallback_proxy(ProxyConfig::new(proxy_all));
+        }
+        config
+    }
+}
@@ -0,0 +1,73 @@
+use crate::model::mapping::files::{
+    load_mapping_file, load_mappings, write_mapping_file, LoadedMapping,
+};
+use crate::model::mapping::StubMappingStub;
+use crate::server::config::MAPPINGS_DIR;
+use serde_json::json;
+use std::path::Path;
+use std::process::ExitCode;
+
+pub fn import(file: &Path, root_dir: &Path) -> ExitCode {
+    let Some(mappings) = valid_mappings(load_mapping_file(file)) else {
+        return ExitCode::FAILURE;
+    };
+
+    let dir = root_dir.join(MAPPINGS_DIR);
+    for mut mapping in mappings {
+        mapping
+            .id
+            .get_or_insert_with(|| uuid::Uuid::new_v4().to_string());
+        match write_mapping_file(&dir, &mapping) {
+            Ok(path) => println!("Imported {}", path.display()),
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+pub fn export(root_dir: &Path, output: Option<&Path>) -> ExitCode {
+    let dir = root_dir.join(MAPPINGS_DIR);
+    let loaded = match load_mappings(&dir) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("Cannot read mappings from {} : {}", dir.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(mappings) = valid_mappings(loaded) else {
+        return ExitCode::FAILURE;
+    };
+
+    let json = serde_json::to_string_pretty(&json!({ "mappings": mappings }))
+        .expect("Mappings are serializable");
+    match output {
+        Some(output) => {
+            if let Err(e) = std::fs::write(output, json) {
+                eprintln!("Cannot write {} : {}", output.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => println!("{}", json),
+    }
+    ExitCode::SUCCESS
+}
+
+fn valid_mappings(loaded: Vec<LoadedMapping>) -> Option<Vec<StubMappingStub>> {
+    let mut mappings = Vec::new();
+    let mut valid = true;
+    for loaded in loaded {
+        match loaded.mapping {
+            Ok(mapping) => mappings.push(mapping),
+            Err(errors) => {
+                valid = false;
+                for error in errors {
+                    eprintln!("{} : {}", loaded.path.display(), error);
+                }
+            }
+        }
+    }
+    valid.then_some(mappings)
+}
@@ -0,0 +1,35 @@
+use crate::model::mapping::files::load_mappings;
+use std::path::Path;
+use std::process::ExitCode;
+
+pub fn validate(dir: &Path) -> ExitCode {
+    let loaded = match load_mappings(dir) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("Cannot read mappings from {} : {}", dir.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut invalid = 0;
+    for loaded in loaded.iter() {
+        if let Err(errors) = loaded.mapping.as_ref() {
+            invalid += 1;
+            for error in errors {
+                eprintln!("{} : {}", loaded.path.display(), error);
+            }
+        }
+    }
+
+    println!(
+        "{} mappings checked, {} valid, {} invalid",
+        loaded.len(),
+        loaded.len() - invalid,
+        invalid
+    );
+    if invalid == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
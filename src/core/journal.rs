@@ -0,0 +1,168 @@
+use crate::model::response::definition::headers_to_json;
+use crate::net::request::{BodyStatus, Request};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+#[derive(Clone, Debug)]
+pub struct JournalSettings {
+    pub enabled: bool,
+    pub max_entries: Option<usize>,
+}
+
+impl Default for JournalSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedRequest {
+    pub id: String,
+    pub method: String,
+    pub url: String,
+    pub absolute_url: String,
+    pub http_version: String,
+    pub headers: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listener: Option<String>,
+    pub body: String,
+    pub body_as_base64: String,
+    pub raw_body_as_base64: String,
+    pub body_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_error: Option<String>,
+    pub logged_date: String,
+    pub was_matched: bool,
+}
+
+impl LoggedRequest {
+    fn new(request: &Request, was_matched: bool) -> Self {
+        let url = match request.url.query() {
+            Some(query) => format!("{}?{}", request.url.path(), query),
+            None => request.url.path().to_string(),
+        };
+
+        let (body_size, body_sha256, body_error) = match &request.body_status {
+            BodyStatus::Complete => (request.raw_body.len() as u64, None, None),
+            BodyStatus::Digested { size, sha256 } => (*size, Some(sha256.clone()), None),
+            BodyStatus::TooLarge { max_size } => (
+                request.raw_body.len() as u64,
+                None,
+                Some(format!("larger than {} bytes", max_size)),
+            ),
+            BodyStatus::Failed(e) => (request.raw_body.len() as u64, None, Some(e.clone())),
+        };
+
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            method: request.method.to_string(),
+            url,
+            absolute_url: request.url.to_string(),
+            http_version: format!("{:?}", request.version),
+            headers: headers_to_json(&request.headers),
+            client_ip: request.client_ip.map(|it| it.to_string()),
+            remote_address: request.remote_address.map(|it| it.to_string()),
+            listener: request.listener.clone(),
+            body: String::from_utf8_lossy(&request.body).to_string(),
+            body_as_base64: BASE64_STANDARD.encode(&request.body),
+            raw_body_as_base64: BASE64_STANDARD.encode(&request.raw_body),
+            body_size,
+            body_sha256,
+            body_error,
+            logged_date: chrono::Utc::now().to_rfc3339(),
+            was_matched,
+        }
+    }
+}
+
+pub struct RequestJournal {
+    settings: JournalSettings,
+    requests: Mutex<VecDeque<LoggedRequest>>,
+}
+
+impl RequestJournal {
+    pub fn new(settings: JournalSettings) -> Self {
+        Self {
+            settings,
+            requests: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    pub(crate) fn record(&self, request: &Request, was_matched: bool) {
+        if !self.settings.enabled {
+            return;
+        }
+
+        let mut requests = self.requests.lock().expect("Request journal lock poisoned");
+        requests.push_back(LoggedRequest::new(request, was_matched));
+        if let Some(max_entries) = self.settings.max_entries {
+            while requests.len() > max_entries {
+                requests.pop_front();
+            }
+        }
+    }
+
+    pub fn requests(&self) -> Vec<LoggedRequest> {
+        self.requests
+            .lock()
+            .expect("Request journal lock poisoned")
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    pub fn reset(&self) {
+        self.requests
+            .lock()
+            .expect("Request journal lock poisoned")
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::{HeaderMap, Method, Version};
+
+    #[test]
+    fn logs_the_raw_body_next_to_the_decoded_one() {
+        let request = Request {
+            url: "http://localhost/".parse().unwrap(),
+            method: Method::POST,
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+            body: b"decoded".to_vec(),
+            raw_body: b"raw".to_vec(),
+            client_certificate: None,
+            body_status: BodyStatus::Complete,
+            remote_address: None,
+            client_ip: None,
+            listener: None,
+        };
+
+        let logged = LoggedRequest::new(&request, false);
+        assert_eq!(logged.body, "decoded");
+        assert_eq!(logged.body_as_base64, BASE64_STANDARD.encode("decoded"));
+        assert_eq!(logged.raw_body_as_base64, BASE64_STANDARD.encode("raw"));
+        assert_eq!(logged.body_size, 3);
+    }
+}
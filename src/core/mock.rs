@@ -1,4 +1,11 @@
+use crate::error::SpoofError;
+use crate::model::mapping::StubMappingStub;
 use crate::net::request::Request;
+use crate::response::response_template::MockResponseBuilder;
+use crate::response::sequence::{ResponseSequence, SequencePolicy};
+use crate::response::templating::SharedRng;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 pub(crate) struct Matcher(Box<dyn Match>);
 
@@ -6,25 +13,82 @@ impl Match for Matcher {
     fn matches(&self, request: &Request) -> bool {
         self.0.matches(request)
     }
+
+    fn path_parameters(&self, request: &Request) -> Option<HashMap<String, String>> {
+        self.0.path_parameters(request)
+    }
 }
 
 pub struct StubMappingBuilder {
     pub(crate) matchers: Vec<Matcher>,
+    pub(crate) responses: Vec<(MockResponseBuilder, u32)>,
+    pub(crate) sequence_policy: SequencePolicy,
 }
 
 pub struct Mock {
     pub(crate) matchers: Vec<Matcher>,
+    pub(crate) responses: ResponseSequence,
+    pub(crate) file_source: Option<FileSource>,
+    pub(crate) mapping: Option<StubMappingStub>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct FileSource {
+    pub(crate) location: String,
+    pub(crate) path: PathBuf,
+    pub(crate) shared: bool,
+    pub(crate) content: String,
+}
+
+impl FileSource {
+    pub(crate) fn own_file(path: PathBuf, mapping: &StubMappingStub) -> Self {
+        Self {
+            location: path.display().to_string(),
+            path,
+            shared: false,
+            content: serde_json::to_string(mapping).unwrap_or_default(),
+        }
+    }
 }
 
 impl Mock {
+    pub(crate) fn try_from_mapping(mapping: StubMappingStub) -> Result<Self, SpoofError> {
+        let mut mock = StubMappingBuilder::try_from(&mapping)?.build();
+        mock.mapping = Some(mapping);
+        Ok(mock)
+    }
+
+    pub(crate) fn id(&self) -> Option<&str> {
+        self.mapping
+            .as_ref()
+            .and_then(|mapping| mapping.id.as_deref())
+    }
+
     pub fn stub_for<M>(matcher: M) -> StubMappingBuilder
     where
         M: 'static + Match,
     {
         StubMappingBuilder {
             matchers: vec![Matcher(Box::new(matcher))],
+            responses: Vec::new(),
+            sequence_policy: SequencePolicy::default(),
         }
     }
+
+    pub(crate) fn matches(&self, request: &Request) -> bool {
+        self.matchers.iter().all(|matcher| matcher.matches(request))
+    }
+
+    pub(crate) fn path_parameters(&self, request: &Request) -> HashMap<String, String> {
+        self.matchers
+            .iter()
+            .find_map(|matcher| matcher.path_parameters(request))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn next_response(&self, rng: &SharedRng) -> &MockResponseBuilder {
+        self.responses.next_response(rng)
+    }
 }
 
 impl StubMappingBuilder {
@@ -32,10 +96,46 @@ impl StubMappingBuilder {
         self.matchers.push(Matcher(Box::new(matcher)));
         self
     }
+
+    pub fn will_return(self, response: MockResponseBuilder) -> Self {
+        self.will_return_in_sequence(vec![response])
+    }
+
+    pub fn will_return_in_sequence(mut self, responses: Vec<MockResponseBuilder>) -> Self {
+        self.responses = responses
+            .into_iter()
+            .map(|response| (response, 1))
+            .collect();
+        self
+    }
+
+    pub fn will_return_weighted(mut self, responses: Vec<(MockResponseBuilder, u32)>) -> Self {
+        self.responses = responses;
+        self.sequence_policy = SequencePolicy::Random;
+        self
+    }
+
+    pub fn with_sequence_policy(mut self, policy: SequencePolicy) -> Self {
+        self.sequence_policy = policy;
+        self
+    }
+
+    pub(crate) fn build(self) -> Mock {
+        Mock {
+            matchers: self.matchers,
+            responses: ResponseSequence::new(self.responses, self.sequence_policy),
+            file_source: None,
+            mapping: None,
+        }
+    }
 }
 
 pub trait Match: Send + Sync {
     fn matches(&self, request: &Request) -> bool;
+
+    fn path_parameters(&self, _request: &Request) -> Option<HashMap<String, String>> {
+        None
+    }
 }
 
 impl<F> Match for F
@@ -1 +1,3 @@
+pub mod journal;
 pub mod mock;
+pub mod registry;
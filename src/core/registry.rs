@@ -0,0 +1,173 @@
+use crate::core::mock::{FileSource, Mock};
+use crate::model::mapping::StubMappingStub;
+use crate::net::request::Request;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+#[derive(Default)]
+pub struct StubRegistry {
+    mocks: RwLock<Vec<Arc<Mock>>>,
+}
+
+impl StubRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn register_mock(&self, mock: Mock) {
+        self.mocks
+            .write()
+            .expect("Stub registry lock poisoned")
+            .push(Arc::new(mock));
+    }
+
+    pub(crate) fn replace_file_stubs(&self, file_mocks: Vec<Mock>) -> Vec<FileSource> {
+        let file_mocks = file_mocks.into_iter().map(Arc::new).collect::<Vec<_>>();
+        let loaded_ids = file_mocks
+            .iter()
+            .filter_map(|mock| mock.id())
+            .collect::<HashSet<_>>();
+
+        let mut mocks = self.mocks.write().expect("Stub registry lock poisoned");
+        let (replaced, kept): (Vec<_>, Vec<_>) =
+            mocks.drain(..).partition(|mock| mock.file_source.is_some());
+        let kept = kept
+            .into_iter()
+            .filter(|mock| mock.id().is_none_or(|id| !loaded_ids.contains(id)))
+            .collect::<Vec<_>>();
+        *mocks = file_mocks.into_iter().chain(kept).collect();
+
+        replaced
+            .into_iter()
+            .filter_map(|mock| mock.file_source.clone())
+            .collect()
+    }
+
+    pub(crate) fn mappings(&self) -> Vec<StubMappingStub> {
+        self.mocks
+            .read()
+            .expect("Stub registry lock poisoned")
+            .iter()
+            .rev()
+            .filter_map(|mock| mock.mapping.clone())
+            .collect()
+    }
+
+    pub(crate) fn runtime_mappings(&self) -> Vec<StubMappingStub> {
+        self.mocks
+            .read()
+            .expect("Stub registry lock poisoned")
+            .iter()
+            .filter(|mock| mock.file_source.is_none())
+            .filter_map(|mock| mock.mapping.clone())
+            .collect()
+    }
+
+    pub(crate) fn find_mapping(&self, id: &str) -> Option<StubMappingStub> {
+        self.mocks
+            .read()
+            .expect("Stub registry lock poisoned")
+            .iter()
+            .rev()
+            .find(|mock| mock.id() == Some(id))
+            .and_then(|mock| mock.mapping.clone())
+    }
+
+    pub(crate) fn removable<P>(&self, predicate: P) -> Result<Vec<Arc<Mock>>, Vec<String>>
+    where
+        P: Fn(&StubMappingStub) -> bool,
+    {
+        let mocks = self.mocks.read().expect("Stub registry lock poisoned");
+        let removable = mocks
+            .iter()
+            .filter(|mock| mock.mapping.as_ref().is_some_and(&predicate))
+            .cloned()
+            .collect::<Vec<_>>();
+        let shared = removable
+            .iter()
+            .filter_map(|mock| mock.file_source.as_ref())
+            .filter(|source| source.shared)
+            .map(|source| source.location.clone())
+            .collect::<Vec<_>>();
+        if shared.is_empty() {
+            Ok(removable)
+        } else {
+            Err(shared)
+        }
+    }
+
+    pub(crate) fn remove_mocks(&self, removed: &[Arc<Mock>]) {
+        self.mocks
+            .write()
+            .expect("Stub registry lock poisoned")
+            .retain(|mock| !removed.iter().any(|it| Arc::ptr_eq(it, mock)));
+    }
+
+    pub(crate) fn find_match(&self, request: &Request) -> Option<Arc<Mock>> {
+        self.mocks
+            .read()
+            .expect("Stub registry lock poisoned")
+            .iter()
+            .rev()
+            .find(|mock| mock.matches(request))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn mock(id: &str, pointer: &str) -> Mock {
+        let mapping = json!({ "id": id, "request": { "pathEquals": "/" } });
+        let mut mock = Mock::try_from_mapping(serde_json::from_value(mapping).unwrap()).unwrap();
+        mock.file_source = Some(FileSource {
+            location: format!("{}.json#{}", id, pointer),
+            path: PathBuf::from(format!("{}.json", id)),
+            shared: !pointer.is_empty(),
+            content: String::new(),
+        });
+        mock
+    }
+
+    #[test]
+    fn removes_stubs_loaded_from_their_own_file() {
+        let registry = StubRegistry::new();
+        registry.register_mock(mock("a", ""));
+
+        let removed = registry
+            .removable(|it| it.id.as_deref() == Some("a"))
+            .unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(
+            removed[0].file_source.as_ref().unwrap().path,
+            PathBuf::from("a.json")
+        );
+        assert!(registry.find_mapping("a").is_some());
+
+        registry.remove_mocks(&removed);
+        assert!(registry.find_mapping("a").is_none());
+    }
+
+    #[test]
+    fn keeps_stubs_loaded_from_a_shared_file() {
+        let registry = StubRegistry::new();
+        registry.register_mock(mock("a", "/mappings/0"));
+
+        match registry.removable(|it| it.id.as_deref() == Some("a")) {
+            Err(locations) => assert_eq!(locations, ["a.json#/mappings/0"]),
+            Ok(_) => panic!("stub in a shared file was removed"),
+        }
+        assert!(registry.find_mapping("a").is_some());
+    }
+
+    #[test]
+    fn an_unknown_id_has_nothing_to_remove() {
+        let registry = StubRegistry::new();
+        registry.register_mock(mock("a", ""));
+        let removed = registry.removable(|it| it.id.as_deref() == Some("b"));
+        assert!(removed.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SpoofError {
+    InvalidRegex {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidPath {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidMethod {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidHeaderName {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidHeaderValue {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidStatusCode {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidJson {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidAddress {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidHttpVersion {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidUrl {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidMatcher {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    InvalidBody {
+        field: String,
+        reason: String,
+    },
+    MissingField {
+        field: String,
+    },
+}
+
+impl SpoofError {
+    pub(crate) fn regex(field: &str, value: &str, error: regex::Error) -> Self {
+        // Regex errors repeat the pattern over several lines, the last one holds the reason
+        let reason = error.to_string();
+        SpoofError::InvalidRegex {
+            field: field.to_string(),
+            value: format!("{:?}", value),
+            reason: reason.lines().last().unwrap_or_default().trim().to_string(),
+        }
+    }
+
+    pub(crate) fn missing(field: &str) -> Self {
+        SpoofError::MissingField {
+            field: field.to_string(),
+        }
+    }
+
+    pub fn field(&self) -> &str {
+        match self {
+            SpoofError::InvalidRegex { field, .. }
+            | SpoofError::InvalidPath { field, .. }
+            | SpoofError::InvalidMethod { field, .. }
+            | SpoofError::InvalidHeaderName { field, .. }
+            | SpoofError::InvalidHeaderValue { field, .. }
+            | SpoofError::InvalidStatusCode { field, .. }
+            | SpoofError::InvalidJson { field, .. }
+            | SpoofError::InvalidAddress { field, .. }
+            | SpoofError::InvalidHttpVersion { field, .. }
+            | SpoofError::InvalidUrl { field, .. }
+            | SpoofError::InvalidMatcher { field, .. }
+            | SpoofError::InvalidBody { field, .. }
+            | SpoofError::MissingField { field } => field,
+        }
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        match self {
+            SpoofError::InvalidRegex { field, .. }
+            | SpoofError::InvalidPath { field, .. }
+            | SpoofError::InvalidMethod { field, .. }
+            | SpoofError::InvalidHeaderName { field, .. }
+            | SpoofError::InvalidHeaderValue { field, .. }
+            | SpoofError::InvalidStatusCode { field, .. }
+            | SpoofError::InvalidJson { field, .. }
+            | SpoofError::InvalidAddress { field, .. }
+            | SpoofError::InvalidHttpVersion { field, .. }
+            | SpoofError::InvalidUrl { field, .. }
+            | SpoofError::InvalidMatcher { field, .. }
+            | SpoofError::InvalidBody { field, .. }
+            | SpoofError::MissingField { field } => field,
+        }
+    }
+
+    pub fn at<T: Into<String>>(mut self, field: T) -> Self {
+        *self.field_mut() = field.into();
+        self
+    }
+
+    pub fn within(mut self, parent: &str) -> Self {
+        let field = self.field_mut();
+        *field = format!("{}{}", parent, field);
+        self
+    }
+
+    pub fn is_missing(&self) -> bool {
+        matches!(self, SpoofError::MissingField { .. })
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            SpoofError::InvalidRegex { value, reason, .. } => {
+                format!("invalid regex {} : {}", value, reason)
+            }
+            SpoofError::InvalidPath { value, reason, .. } => {
+                format!("invalid path {} : {}", value, reason)
+            }
+            SpoofError::InvalidMethod { value, reason, .. } => {
+                format!("invalid HTTP method {} : {}", value, reason)
+            }
+            SpoofError::InvalidHeaderName { value, reason, .. } => {
+                format!("invalid header name {} : {}", value, reason)
+            }
+            SpoofError::InvalidHeaderValue { value, reason, .. } => {
+                format!("invalid header value {} : {}", value, reason)
+            }
+            SpoofError::InvalidStatusCode { value, reason, .. } => {
+                format!("invalid status code {} : {}", value, reason)
+            }
+            SpoofError::InvalidJson { value, reason, .. } => {
+                format!("invalid JSON {} : {}", value, reason)
+            }
+            SpoofError::InvalidAddress { value, reason, .. } => {
+                format!("invalid IP address or network {} : {}", value, reason)
+            }
+            SpoofError::InvalidHttpVersion { value, reason, .. } => {
+                format!("invalid HTTP version {} : {}", value, reason)
+            }
+            SpoofError::InvalidUrl { value, reason, .. } => {
+                format!("invalid URL {} : {}", value, reason)
+            }
+            SpoofError::InvalidMatcher { value, reason, .. } => {
+                format!("invalid matcher {} : {}", value, reason)
+            }
+            SpoofError::InvalidBody { reason, .. } => format!("invalid body : {}", reason),
+            SpoofError::MissingField { .. } => "missing field".to_string(),
+        }
+    }
+}
+
+impl Display for SpoofError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} : {}", self.field(), self.message())
+    }
+}
+
+impl std::error::Error for SpoofError {}
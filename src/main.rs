@@ -1,6 +1,11 @@
+use crate::cli::{explain, transfer, validate, Cli, Command, ServeArgs};
 use crate::standalone::start_standalone_server;
+use clap::Parser;
+use std::process::ExitCode;
 
+mod cli;
 mod core;
+pub mod error;
 pub mod matcher;
 mod model;
 mod net;
@@ -9,14 +14,35 @@ pub mod server;
 pub mod standalone;
 
 #[tokio::main]
-async fn main() {
-    env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
 
-    print_cli_banner();
+    match cli.command {
+        None => serve(cli.serve).await,
+        Some(Command::Serve(args)) => serve(*args).await,
+        Some(Command::Validate { dir }) => validate::validate(&dir),
+        Some(Command::Import { file, root_dir }) => transfer::import(&file, &root_dir),
+        Some(Command::Export { root_dir, output }) => {
+            transfer::export(&root_dir, output.as_deref())
+        }
+        Some(Command::Explain { mappings, request }) => explain::explain(&mappings, &request),
+    }
+}
 
-    start_standalone_server(3000, false, shutdown_signal())
-        .await
-        .expect("Error in running server");
+async fn serve(args: ServeArgs) -> ExitCode {
+    env_logger::init_from_env(env_logger::Env::default().default_filter_or(args.log_level()));
+
+    if !args.disable_banner {
+        print_cli_banner();
+    }
+
+    match start_standalone_server(args.server_config(), shutdown_signal()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            log::error!("Cannot run the server : {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
 
 fn print_cli_banner() {
@@ -36,8 +62,6 @@ fn print_cli_banner() {
 
 #[cfg(not(target_os = "windows"))]
 async fn shutdown_signal() {
-    let mut hangup_stream = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
-        .expect("Cannot install SIGINT signal handler");
     let mut sigint_stream =
         tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())
             .expect("Cannot install SIGINT signal handler");
@@ -46,7 +70,6 @@ async fn shutdown_signal() {
             .expect("Cannot install SIGINT signal handler");
 
     tokio::select! {
-        _val = hangup_stream.recv() => log::trace!("Received SIGINT"),
         _val = sigint_stream.recv() => log::trace!("Received SIGINT"),
         _val = sigterm_stream.recv() => log::trace!("Received SIGTERM"),
     }
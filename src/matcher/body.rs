@@ -1,13 +1,15 @@
 use crate::core::mock::Match;
+use crate::error::SpoofError;
+use crate::matcher::json::{ExpectedJson, JsonCompareOptions};
 use crate::net::request::Request;
-use assert_json_diff::{assert_json_matches_no_panic, CompareMode};
 use log::debug;
+use regex::Regex;
 use serde::Serialize;
 use serde_json::Value;
 
 enum Body {
     Bytes(Vec<u8>),
-    Json(Value),
+    Json(ExpectedJson),
 }
 
 pub struct BodyExactMatcher(Body);
@@ -33,17 +35,28 @@ impl BodyExactMatcher {
     where
         T: Serialize,
     {
-        let body = serde_json::to_value(body).expect("Cannot parse object as JSON");
-        Self(Body::Json(body))
+        Self::try_json(body).unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn json_string<T>(body: T) -> Self
     where
         T: AsRef<[u8]>,
     {
-        let body = serde_json::from_slice::<Value>(body.as_ref())
-            .expect("Cannot parse field as JSON string");
-        Self(Body::Json(body))
+        Self::try_json_string(body).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_json<T>(body: T) -> Result<Self, SpoofError>
+    where
+        T: Serialize,
+    {
+        to_json(body).map(|body| Self(Body::Json(ExpectedJson::new(body))))
+    }
+
+    pub fn try_json_string<T>(body: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<[u8]>,
+    {
+        parse_json(body.as_ref()).map(|body| Self(Body::Json(ExpectedJson::new(body))))
     }
 }
 
@@ -53,7 +66,7 @@ impl Match for BodyExactMatcher {
             Body::Bytes(bytes) => request.body == *bytes,
             Body::Json(json) => {
                 if let Ok(body) = serde_json::from_slice::<Value>(&request.body) {
-                    body == *json
+                    json.matches(&body, &JsonCompareOptions::strict())
                 } else {
                     false
                 }
@@ -95,32 +108,174 @@ impl Match for BodyContainsMatcher {
     }
 }
 
-pub struct BodyPartialJsonMatcher(Value);
+pub struct BodyPartialJsonMatcher(BodyJsonMatcher);
 
 impl BodyPartialJsonMatcher {
     pub fn json<T>(body: T) -> Self
     where
         T: Serialize,
     {
-        Self(serde_json::to_value(body).expect("Cannot serialize to JSON"))
+        Self::try_json(body).unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn json_string<T>(body: T) -> Self
     where
         T: AsRef<str>,
     {
-        Self(serde_json::from_str(body.as_ref()).expect("Cannot deserialize to JSON"))
+        Self::try_json_string(body).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_json<T>(body: T) -> Result<Self, SpoofError>
+    where
+        T: Serialize,
+    {
+        BodyJsonMatcher::try_json(body).map(|it| Self(it.ignore_extra_elements(true)))
+    }
+
+    pub fn try_json_string<T>(body: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        BodyJsonMatcher::try_json_string(body).map(|it| Self(it.ignore_extra_elements(true)))
+    }
+
+    pub fn ignore_array_order(self, ignore_array_order: bool) -> Self {
+        Self(self.0.ignore_array_order(ignore_array_order))
+    }
+
+    pub fn numeric_tolerance(self, numeric_tolerance: f64) -> Self {
+        Self(self.0.numeric_tolerance(numeric_tolerance))
     }
 }
 
 impl Match for BodyPartialJsonMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        // Request JSON must include the expected JSON
+        self.0.matches(request)
+    }
+}
+
+pub struct BodyRegexMatcher(Regex);
+
+impl BodyRegexMatcher {
+    pub fn new<T>(regex: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::try_new(regex).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(regex: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        Regex::new(regex.as_ref())
+            .map(Self)
+            .map_err(|e| SpoofError::regex("regex", regex.as_ref(), e))
+    }
+}
+
+impl Match for BodyRegexMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        match std::str::from_utf8(&request.body) {
+            Ok(body) => self.0.is_match(body),
+            Err(e) => {
+                debug!("Cannot convert request body to string : {}", e);
+                false
+            }
+        }
+    }
+}
+
+pub struct BodyJsonMatcher {
+    expected: ExpectedJson,
+    options: JsonCompareOptions,
+}
+
+impl BodyJsonMatcher {
+    pub fn json<T>(body: T) -> Self
+    where
+        T: Serialize,
+    {
+        Self::try_json(body).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn json_string<T>(body: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::try_json_string(body).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_json<T>(body: T) -> Result<Self, SpoofError>
+    where
+        T: Serialize,
+    {
+        to_json(body).map(Self::from_value)
+    }
+
+    pub fn try_json_string<T>(body: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        parse_json(body.as_ref().as_bytes()).map(Self::from_value)
+    }
+
+    fn from_value(expected: Value) -> Self {
+        Self {
+            expected: ExpectedJson::new(expected),
+            options: JsonCompareOptions::strict(),
+        }
+    }
+
+    pub fn with_options(mut self, options: JsonCompareOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn ignore_array_order(mut self, ignore_array_order: bool) -> Self {
+        self.options = self.options.ignore_array_order(ignore_array_order);
+        self
+    }
+
+    pub fn ignore_extra_elements(mut self, ignore_extra_elements: bool) -> Self {
+        self.options = self.options.ignore_extra_elements(ignore_extra_elements);
+        self
+    }
+
+    pub fn numeric_tolerance(mut self, numeric_tolerance: f64) -> Self {
+        self.options = self.options.numeric_tolerance(numeric_tolerance);
+        self
+    }
+}
+
+impl BodyJsonMatcher {
+    pub(crate) fn matches_json(&self, actual: &Value) -> bool {
+        self.expected.matches(actual, &self.options)
+    }
+}
+
+impl Match for BodyJsonMatcher {
     fn matches(&self, request: &Request) -> bool {
         if let Ok(body) = serde_json::from_slice::<Value>(&request.body) {
-            let config = assert_json_diff::Config::new(CompareMode::Inclusive);
-            // Assert Request JSON includes expected JSON
-            assert_json_matches_no_panic(&body, &self.0, config).is_ok()
+            self.matches_json(&body)
         } else {
             false
         }
     }
 }
+
+fn to_json<T: Serialize>(body: T) -> Result<Value, SpoofError> {
+    serde_json::to_value(body).map_err(|e| SpoofError::InvalidBody {
+        field: "body".to_string(),
+        reason: e.to_string(),
+    })
+}
+
+fn parse_json(body: &[u8]) -> Result<Value, SpoofError> {
+    serde_json::from_slice(body).map_err(|e| SpoofError::InvalidJson {
+        field: "body".to_string(),
+        value: format!("{:?}", String::from_utf8_lossy(body)),
+        reason: e.to_string(),
+    })
+}
@@ -0,0 +1,93 @@
+use crate::core::mock::Match;
+use crate::error::SpoofError;
+use crate::net::request::Request;
+use regex::Regex;
+
+pub enum ClientCertificateMatcher {
+    Present(bool),
+    SubjectMatches(Regex),
+    IssuerMatches(Regex),
+    SubjectAltName(String),
+    Sha256Fingerprint(String),
+}
+
+impl ClientCertificateMatcher {
+    pub fn present() -> Self {
+        Self::Present(true)
+    }
+
+    pub fn absent() -> Self {
+        Self::Present(false)
+    }
+
+    pub fn subject_matches<T>(regex: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::try_subject_matches(regex).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn issuer_matches<T>(regex: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::try_issuer_matches(regex).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_subject_matches<T>(regex: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        Regex::new(regex.as_ref())
+            .map(Self::SubjectMatches)
+            .map_err(|e| SpoofError::regex("regex", regex.as_ref(), e))
+    }
+
+    pub fn try_issuer_matches<T>(regex: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        Regex::new(regex.as_ref())
+            .map(Self::IssuerMatches)
+            .map_err(|e| SpoofError::regex("regex", regex.as_ref(), e))
+    }
+
+    pub fn subject_alt_name<T>(name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::SubjectAltName(name.into())
+    }
+
+    pub fn sha256_fingerprint<T>(fingerprint: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::Sha256Fingerprint(fingerprint.as_ref().replace(':', "").to_lowercase())
+    }
+}
+
+impl Match for ClientCertificateMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        let certificate = request.client_certificate.as_deref();
+        match (self, certificate) {
+            (ClientCertificateMatcher::Present(present), certificate) => {
+                certificate.is_some() == *present
+            }
+            (_, None) => false,
+            (ClientCertificateMatcher::SubjectMatches(regex), Some(certificate)) => {
+                regex.is_match(&certificate.subject)
+            }
+            (ClientCertificateMatcher::IssuerMatches(regex), Some(certificate)) => {
+                regex.is_match(&certificate.issuer)
+            }
+            (ClientCertificateMatcher::SubjectAltName(name), Some(certificate)) => certificate
+                .subject_alt_names
+                .iter()
+                .any(|it| it.eq_ignore_ascii_case(name)),
+            (ClientCertificateMatcher::Sha256Fingerprint(fingerprint), Some(certificate)) => {
+                certificate.sha256_fingerprint == *fingerprint
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::core::mock::Match;
+use crate::error::SpoofError;
+use crate::net::request::Request;
+use std::net::IpAddr;
+
+pub struct ClientIpMatcher(Vec<IpNetwork>);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct IpNetwork {
+    address: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    fn parse(cidr: &str) -> Result<Self, SpoofError> {
+        let invalid = |reason: &str| SpoofError::InvalidAddress {
+            field: "cidr".to_string(),
+            value: format!("{:?}", cidr),
+            reason: reason.to_string(),
+        };
+
+        let (address, prefix_len) = match cidr.trim().split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (cidr.trim(), None),
+        };
+        let address = address
+            .parse::<IpAddr>()
+            .map_err(|e| invalid(&e.to_string()))?;
+        let max_len = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|it| *it <= max_len)
+                .ok_or_else(|| invalid(&format!("prefix length must be 0 to {}", max_len)))?,
+            None => max_len,
+        };
+        // An IPv4-mapped network is an IPv4 one when its prefix covers the `::ffff:` part
+        match address.to_canonical() {
+            IpAddr::V4(v4) if address.is_ipv6() && prefix_len >= 96 => Ok(Self {
+                address: IpAddr::V4(v4),
+                prefix_len: prefix_len - 96,
+            }),
+            _ => Ok(Self {
+                address,
+                prefix_len,
+            }),
+        }
+    }
+
+    fn contains(&self, address: IpAddr) -> bool {
+        match (self.address, address.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ClientIpMatcher {
+    pub fn new<T>(cidr: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::try_new(cidr).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(cidr: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        Self::try_any_of([cidr])
+    }
+
+    pub fn any_of<T, I>(cidrs: I) -> Self
+    where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        Self::try_any_of(cidrs).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_any_of<T, I>(cidrs: I) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        cidrs
+            .into_iter()
+            .map(|it| IpNetwork::parse(it.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+impl Match for ClientIpMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        match request.client_ip {
+            Some(client_ip) => self.0.iter().any(|it| it.contains(client_ip)),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains(cidr: &str, address: &str) -> bool {
+        IpNetwork::parse(cidr)
+            .unwrap()
+            .contains(address.parse().unwrap())
+    }
+
+    #[test]
+    fn ipv4_networks_contain_their_addresses() {
+        assert!(contains("10.0.0.0/8", "10.255.1.2"));
+        assert!(!contains("10.0.0.0/8", "11.0.0.1"));
+        assert!(contains("192.168.1.7", "192.168.1.7"));
+        assert!(!contains("192.168.1.7", "192.168.1.8"));
+        assert!(contains("0.0.0.0/0", "203.0.113.9"));
+    }
+
+    #[test]
+    fn ipv6_networks_contain_their_addresses() {
+        assert!(contains("fd00::/8", "fd12:3456::1"));
+        assert!(!contains("fd00::/8", "fe80::1"));
+        assert!(contains("::/0", "2001:db8::1"));
+        assert!(!contains("2001:db8::/32", "10.0.0.1"));
+    }
+
+    #[test]
+    fn ipv4_mapped_addresses_match_ipv4_networks() {
+        assert!(contains("10.0.0.0/8", "::ffff:10.1.2.3"));
+        assert!(contains("::ffff:10.0.0.0/104", "10.1.2.3"));
+    }
+
+    #[test]
+    fn rejects_invalid_networks() {
+        assert!(IpNetwork::parse("10.0.0.0/33").is_err());
+        assert!(IpNetwork::parse("fd00::/129").is_err());
+        assert!(IpNetwork::parse("10.0.0.0/x").is_err());
+        assert!(IpNetwork::parse("localhost").is_err());
+    }
+
+    #[test]
+    fn requests_without_a_client_address_never_match() {
+        let matcher = ClientIpMatcher::any_of(["10.0.0.0/8", "fd00::/8"]);
+        let mut request = Request::get("http://localhost/");
+        assert!(!matcher.matches(&request));
+
+        request.client_ip = Some("fd00::1".parse().unwrap());
+        assert!(matcher.matches(&request));
+        request.client_ip = Some("192.0.2.1".parse().unwrap());
+        assert!(!matcher.matches(&request));
+    }
+}
@@ -1,32 +1,40 @@
 use crate::core::mock::Match;
+use crate::error::SpoofError;
 use crate::net::request::Request;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use http::{HeaderName, HeaderValue};
 use regex::Regex;
 use std::collections::HashSet;
+use std::fmt::{Debug, Display};
 
 pub struct HeaderExactMatcher(HeaderName, Vec<HeaderValue>);
 
 impl HeaderExactMatcher {
     pub fn new<K, V>(key: K, values: Vec<V>) -> Self
     where
-        K: TryInto<HeaderName>,
-        <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-        V: TryInto<HeaderValue>,
-        <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
     {
-        let key = key.try_into().expect("Cannot parse field to header name");
+        Self::try_new(key, values).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<K, V>(key: K, values: Vec<V>) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
+    {
+        let key = header_name(key)?;
         let values = values
             .into_iter()
-            .map(|value| {
-                value
-                    .try_into()
-                    .expect("Cannot parse field to header value")
-            })
-            .collect();
+            .map(header_value)
+            .collect::<Result<_, _>>()?;
 
-        Self(key, values)
+        Ok(Self(key, values))
     }
 }
 
@@ -48,22 +56,111 @@ impl Match for HeaderExactMatcher {
     }
 }
 
-pub struct HeaderExistsMatcher(HeaderName);
+pub struct HeaderExistsMatcher(HeaderName, bool);
 
 impl HeaderExistsMatcher {
     pub fn new<K>(key: K) -> Self
     where
-        K: TryInto<HeaderName>,
-        <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+    {
+        Self::try_new(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn does_not_exist<K>(key: K) -> Self
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+    {
+        Self::try_does_not_exist(key).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<K>(key: K) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+    {
+        Ok(Self(header_name(key)?, true))
+    }
+
+    pub fn try_does_not_exist<K>(key: K) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
     {
-        let key = key.try_into().expect("Cannot parse field to header name");
-        Self(key)
+        Ok(Self(header_name(key)?, false))
     }
 }
 
 impl Match for HeaderExistsMatcher {
     fn matches(&self, request: &Request) -> bool {
-        request.headers.get(&self.0).is_some()
+        request.headers.get(&self.0).is_some() == self.1
+    }
+}
+
+pub struct HeaderCaseInsensitiveMatcher(HeaderName, String);
+
+impl HeaderCaseInsensitiveMatcher {
+    pub fn new<K, V>(key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: Into<String>,
+    {
+        Self::try_new(key, value).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<K, V>(key: K, value: V) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: Into<String>,
+    {
+        Ok(Self(header_name(key)?, value.into()))
+    }
+}
+
+impl Match for HeaderCaseInsensitiveMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        request
+            .headers
+            .get_all(&self.0)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|value| value.eq_ignore_ascii_case(&self.1))
+    }
+}
+
+pub struct HeaderValueSubstringMatcher(HeaderName, String);
+
+impl HeaderValueSubstringMatcher {
+    pub fn new<K, V>(key: K, part: V) -> Self
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: Into<String>,
+    {
+        Self::try_new(key, part).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<K, V>(key: K, part: V) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: Into<String>,
+    {
+        Ok(Self(header_name(key)?, part.into()))
+    }
+}
+
+impl Match for HeaderValueSubstringMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        request
+            .headers
+            .get_all(&self.0)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|value| value.contains(self.1.as_str()))
     }
 }
 
@@ -72,13 +169,21 @@ pub struct HeaderValueRegexMatcher(HeaderName, Regex);
 impl HeaderValueRegexMatcher {
     pub fn new<K>(key: K, value: &str) -> Self
     where
-        K: TryInto<HeaderName>,
-        <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
     {
-        let key = key.try_into().expect("Cannot parse field to header name");
-        let regex = Regex::new(value).expect("Cannot parse value into Regex");
+        Self::try_new(key, value).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        Self(key, regex)
+    pub fn try_new<K>(key: K, value: &str) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+    {
+        let key = header_name(key)?;
+        let regex = Regex::new(value).map_err(|e| SpoofError::regex("value", value, e))?;
+
+        Ok(Self(key, regex))
     }
 }
 
@@ -104,22 +209,28 @@ pub struct HeaderValueContainsMatcher(HeaderName, HashSet<HeaderValue>);
 impl HeaderValueContainsMatcher {
     pub fn new<K, V>(key: K, values: Vec<V>) -> Self
     where
-        K: TryInto<HeaderName>,
-        <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-        V: TryInto<HeaderValue>,
-        <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
     {
-        let key = key.try_into().expect("Cannot parse field to header name");
+        Self::try_new(key, values).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<K, V>(key: K, values: Vec<V>) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
+    {
+        let key = header_name(key)?;
         let values = values
             .into_iter()
-            .map(|value| {
-                value
-                    .try_into()
-                    .expect("Cannot parse field to header value")
-            })
-            .collect::<HashSet<_>>();
+            .map(header_value)
+            .collect::<Result<_, _>>()?;
 
-        Self(key, values)
+        Ok(Self(key, values))
     }
 }
 
@@ -190,3 +301,31 @@ impl Match for BearerTokenMatcher {
         self.0.matches(request)
     }
 }
+
+pub(crate) fn header_name<K>(key: K) -> Result<HeaderName, SpoofError>
+where
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+{
+    let value = format!("{:?}", key);
+    key.try_into().map_err(|e| SpoofError::InvalidHeaderName {
+        field: "key".to_string(),
+        value,
+        reason: e.to_string(),
+    })
+}
+
+pub(crate) fn header_value<V>(value: V) -> Result<HeaderValue, SpoofError>
+where
+    V: TryInto<HeaderValue> + Debug,
+    <V as TryInto<HeaderValue>>::Error: Display,
+{
+    let debug = format!("{:?}", value);
+    value
+        .try_into()
+        .map_err(|e| SpoofError::InvalidHeaderValue {
+            field: "value".to_string(),
+            value: debug,
+            reason: e.to_string(),
+        })
+}
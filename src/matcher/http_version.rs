@@ -0,0 +1,100 @@
+use crate::core::mock::Match;
+use crate::error::SpoofError;
+use crate::net::request::Request;
+use http::Version;
+
+pub struct HttpVersionMatcher(Vec<Version>);
+
+impl HttpVersionMatcher {
+    pub fn new(version: Version) -> Self {
+        Self::any_of([version])
+    }
+
+    pub fn any_of<I>(versions: I) -> Self
+    where
+        I: IntoIterator<Item = Version>,
+    {
+        Self(versions.into_iter().collect())
+    }
+
+    pub fn try_parse<T>(version: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        Self::try_parse_any_of([version])
+    }
+
+    pub fn try_parse_any_of<T, I>(versions: I) -> Result<Self, SpoofError>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        versions
+            .into_iter()
+            .map(|it| parse_version(it.as_ref()))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+pub(crate) fn parse_version(version: &str) -> Result<Version, SpoofError> {
+    let name = version.trim();
+    let number = match name.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("HTTP/") => &name[5..],
+        _ => name,
+    };
+    match number {
+        "0.9" => Ok(Version::HTTP_09),
+        "1.0" => Ok(Version::HTTP_10),
+        "1.1" => Ok(Version::HTTP_11),
+        "2" | "2.0" => Ok(Version::HTTP_2),
+        "3" | "3.0" => Ok(Version::HTTP_3),
+        _ => Err(SpoofError::InvalidHttpVersion {
+            field: "version".to_string(),
+            value: format!("{:?}", version),
+            reason: "expected HTTP/0.9, HTTP/1.0, HTTP/1.1, HTTP/2 or HTTP/3".to_string(),
+        }),
+    }
+}
+
+impl Match for HttpVersionMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        self.0.contains(&request.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_versions_with_or_without_prefix() {
+        assert_eq!(parse_version("HTTP/1.1").unwrap(), Version::HTTP_11);
+        assert_eq!(parse_version("http/1.0").unwrap(), Version::HTTP_10);
+        assert_eq!(parse_version("1.1").unwrap(), Version::HTTP_11);
+        assert_eq!(parse_version(" 2 ").unwrap(), Version::HTTP_2);
+        assert_eq!(parse_version("HTTP/2.0").unwrap(), Version::HTTP_2);
+        assert_eq!(parse_version("3").unwrap(), Version::HTTP_3);
+        assert_eq!(parse_version("0.9").unwrap(), Version::HTTP_09);
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        for version in ["", "HTTP/", "1", "1.2", "HTTP/4", "HTTPS/1.1"] {
+            assert!(matches!(
+                parse_version(version),
+                Err(SpoofError::InvalidHttpVersion { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn matches_any_of_the_versions() {
+        let matcher = HttpVersionMatcher::try_parse_any_of(["1.1", "HTTP/2"]).unwrap();
+        let mut request = Request::get("http://localhost/");
+        assert!(matcher.matches(&request));
+
+        request.version = Version::HTTP_10;
+        assert!(!matcher.matches(&request));
+    }
+}
@@ -0,0 +1,339 @@
+use log::debug;
+use regex::Regex;
+use serde_json::{Number, Value};
+use std::collections::HashMap;
+
+const PLACEHOLDER_PREFIX: &str = "${json-unit.";
+const ANY_STRING: &str = "${json-unit.any-string}";
+const ANY_NUMBER: &str = "${json-unit.any-number}";
+const ANY_BOOLEAN: &str = "${json-unit.any-boolean}";
+const IGNORE: &str = "${json-unit.ignore}";
+const REGEX_PREFIX: &str = "${json-unit.regex}";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct JsonCompareOptions {
+    pub ignore_array_order: bool,
+    pub ignore_extra_elements: bool,
+    pub numeric_tolerance: Option<f64>,
+}
+
+impl JsonCompareOptions {
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            ignore_array_order: true,
+            ignore_extra_elements: true,
+            numeric_tolerance: None,
+        }
+    }
+
+    pub fn ignore_array_order(mut self, ignore_array_order: bool) -> Self {
+        self.ignore_array_order = ignore_array_order;
+        self
+    }
+
+    pub fn ignore_extra_elements(mut self, ignore_extra_elements: bool) -> Self {
+        self.ignore_extra_elements = ignore_extra_elements;
+        self
+    }
+
+    pub fn numeric_tolerance(mut self, numeric_tolerance: f64) -> Self {
+        self.numeric_tolerance = Some(numeric_tolerance);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExpectedJson {
+    value: Value,
+    patterns: HashMap<String, Regex>,
+}
+
+impl ExpectedJson {
+    pub(crate) fn new(value: Value) -> Self {
+        let mut patterns = HashMap::new();
+        collect_patterns(&value, &mut patterns);
+        Self { value, patterns }
+    }
+
+    pub(crate) fn matches(&self, actual: &Value, options: &JsonCompareOptions) -> bool {
+        let comparison = Comparison {
+            options,
+            patterns: &self.patterns,
+        };
+        comparison.matches(actual, &self.value)
+    }
+}
+
+fn collect_patterns(value: &Value, patterns: &mut HashMap<String, Regex>) {
+    match value {
+        Value::String(placeholder) => {
+            if let Some(pattern) = placeholder.strip_prefix(REGEX_PREFIX) {
+                if !patterns.contains_key(pattern) {
+                    match Regex::new(pattern) {
+                        Ok(regex) => {
+                            patterns.insert(pattern.to_string(), regex);
+                        }
+                        Err(e) => debug!("Cannot convert JSON placeholder to regex : {}", e),
+                    }
+                }
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|it| collect_patterns(it, patterns)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|it| collect_patterns(it, patterns)),
+        _ => {}
+    }
+}
+
+struct Comparison<'a> {
+    options: &'a JsonCompareOptions,
+    patterns: &'a HashMap<String, Regex>,
+}
+
+impl Comparison<'_> {
+    fn matches(&self, actual: &Value, expected: &Value) -> bool {
+        match (actual, expected) {
+            (_, Value::String(placeholder)) if placeholder.starts_with(PLACEHOLDER_PREFIX) => {
+                self.placeholder_matches(actual, placeholder)
+            }
+            (Value::Object(actual), Value::Object(expected)) => {
+                if !self.options.ignore_extra_elements && actual.len() != expected.len() {
+                    return false;
+                }
+                expected.iter().all(|(key, expected)| {
+                    actual
+                        .get(key)
+                        .map(|actual| self.matches(actual, expected))
+                        .unwrap_or_default()
+                })
+            }
+            (Value::Array(actual), Value::Array(expected)) => {
+                if actual.len() < expected.len()
+                    || (!self.options.ignore_extra_elements && actual.len() != expected.len())
+                {
+                    return false;
+                }
+                if self.options.ignore_array_order {
+                    self.unordered_matches(actual, expected)
+                } else {
+                    expected
+                        .iter()
+                        .zip(actual)
+                        .all(|(expected, actual)| self.matches(actual, expected))
+                }
+            }
+            (Value::Number(actual), Value::Number(expected)) => {
+                numbers_match(actual, expected, self.options.numeric_tolerance)
+            }
+            (actual, expected) => actual == expected,
+        }
+    }
+
+    fn unordered_matches(&self, actual: &[Value], expected: &[Value]) -> bool {
+        let candidates = expected
+            .iter()
+            .map(|expected| {
+                actual
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, actual)| self.matches(actual, expected))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut paired_with = vec![None; actual.len()];
+        (0..expected.len()).all(|expected| {
+            pair(
+                expected,
+                &candidates,
+                &mut paired_with,
+                &mut vec![false; actual.len()],
+            )
+        })
+    }
+
+    fn placeholder_matches(&self, actual: &Value, placeholder: &str) -> bool {
+        match placeholder {
+            ANY_STRING => actual.is_string(),
+            ANY_NUMBER => actual.is_number(),
+            ANY_BOOLEAN => actual.is_boolean(),
+            IGNORE => true,
+            _ => match placeholder.strip_prefix(REGEX_PREFIX) {
+                // Invalid patterns were left out and match nothing
+                Some(pattern) => self
+                    .patterns
+                    .get(pattern)
+                    .is_some_and(|regex| regex_matches(actual, regex)),
+                // Not a placeholder we know about, compare it as a plain string
+                None => actual.as_str() == Some(placeholder),
+            },
+        }
+    }
+}
+
+fn pair(
+    expected: usize,
+    candidates: &[Vec<usize>],
+    paired_with: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &actual in &candidates[expected] {
+        if visited[actual] {
+            continue;
+        }
+        visited[actual] = true;
+        let available = match paired_with[actual] {
+            Some(other) => pair(other, candidates, paired_with, visited),
+            None => true,
+        };
+        if available {
+            paired_with[actual] = Some(expected);
+            return true;
+        }
+    }
+    false
+}
+
+fn numbers_match(actual: &Number, expected: &Number, tolerance: Option<f64>) -> bool {
+    match (tolerance, actual.as_f64(), expected.as_f64()) {
+        (Some(tolerance), Some(actual), Some(expected)) => (actual - expected).abs() <= tolerance,
+        _ => actual == expected,
+    }
+}
+
+fn regex_matches(actual: &Value, regex: &Regex) -> bool {
+    match actual {
+        Value::String(actual) => regex.is_match(actual),
+        Value::Number(actual) => regex.is_match(&actual.to_string()),
+        Value::Bool(actual) => regex.is_match(&actual.to_string()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn matches(actual: Value, expected: Value, options: JsonCompareOptions) -> bool {
+        ExpectedJson::new(expected).matches(&actual, &options)
+    }
+
+    #[test]
+    fn type_placeholders_match_values_of_their_type() {
+        let expected = json!({
+            "name": "${json-unit.any-string}",
+            "age": "${json-unit.any-number}",
+            "admin": "${json-unit.any-boolean}",
+            "extra": "${json-unit.ignore}"
+        });
+        let strict = JsonCompareOptions::strict();
+        assert!(matches(
+            json!({ "name": "Ann", "age": 41, "admin": false, "extra": [1] }),
+            expected.clone(),
+            strict
+        ));
+        assert!(!matches(
+            json!({ "name": 1, "age": 41, "admin": false, "extra": null }),
+            expected,
+            strict
+        ));
+    }
+
+    #[test]
+    fn regex_placeholder_matches_strings_numbers_and_booleans() {
+        let expected = json!(["${json-unit.regex}^[a-z]+$", "${json-unit.regex}^4\\d$"]);
+        let strict = JsonCompareOptions::strict();
+        assert!(matches(json!(["abc", 42]), expected.clone(), strict));
+        assert!(!matches(json!(["ABC", 42]), expected, strict));
+    }
+
+    #[test]
+    fn invalid_regex_placeholder_matches_nothing() {
+        let strict = JsonCompareOptions::strict();
+        assert!(!matches(json!("("), json!("${json-unit.regex}("), strict));
+    }
+
+    #[test]
+    fn unknown_placeholder_is_a_plain_string() {
+        let strict = JsonCompareOptions::strict();
+        assert!(matches(
+            json!("${json-unit.other}"),
+            json!("${json-unit.other}"),
+            strict
+        ));
+        assert!(!matches(json!("x"), json!("${json-unit.other}"), strict));
+    }
+
+    #[test]
+    fn unordered_arrays_backtrack_over_placeholders() {
+        let options = JsonCompareOptions::strict().ignore_array_order(true);
+        assert!(matches(
+            json!(["a", "b"]),
+            json!(["${json-unit.any-string}", "a"]),
+            options
+        ));
+        assert!(matches(
+            json!([{ "id": 1 }, { "id": 2 }]),
+            json!([{ "id": "${json-unit.any-number}" }, { "id": 1 }]),
+            options
+        ));
+        assert!(!matches(
+            json!(["a", "b"]),
+            json!(["${json-unit.any-string}", "c"]),
+            options
+        ));
+    }
+
+    #[test]
+    fn unordered_arrays_pair_each_element_once() {
+        let options = JsonCompareOptions::lenient();
+        assert!(!matches(json!(["a", "b"]), json!(["a", "a"]), options));
+        assert!(matches(json!(["b", "a", "a"]), json!(["a", "a"]), options));
+    }
+
+    #[test]
+    fn ordered_arrays_compare_position_by_position() {
+        let strict = JsonCompareOptions::strict();
+        assert!(!matches(json!(["b", "a"]), json!(["a", "b"]), strict));
+        assert!(!matches(json!(["a", "b", "c"]), json!(["a", "b"]), strict));
+        assert!(matches(
+            json!(["a", "b", "c"]),
+            json!(["a", "b"]),
+            strict.ignore_extra_elements(true)
+        ));
+    }
+
+    #[test]
+    fn extra_fields_need_ignore_extra_elements() {
+        let actual = json!({ "a": 1, "b": 2 });
+        assert!(!matches(
+            actual.clone(),
+            json!({ "a": 1 }),
+            JsonCompareOptions::strict()
+        ));
+        assert!(matches(
+            actual,
+            json!({ "a": 1 }),
+            JsonCompareOptions::lenient()
+        ));
+    }
+
+    #[test]
+    fn numbers_match_within_tolerance() {
+        let options = JsonCompareOptions::strict().numeric_tolerance(0.01);
+        assert!(matches(json!(1.005), json!(1), options));
+        assert!(!matches(json!(1.1), json!(1), options));
+        assert!(!matches(
+            json!(1.005),
+            json!(1),
+            JsonCompareOptions::strict()
+        ));
+    }
+}
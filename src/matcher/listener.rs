@@ -0,0 +1,30 @@
+use crate::core::mock::Match;
+use crate::net::request::Request;
+
+pub struct ListenerMatcher(Vec<String>);
+
+impl ListenerMatcher {
+    pub fn new<T>(tag: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::any_of([tag])
+    }
+
+    pub fn any_of<T, I>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self(tags.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Match for ListenerMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        match request.listener.as_ref() {
+            Some(listener) => self.0.contains(listener),
+            None => false,
+        }
+    }
+}
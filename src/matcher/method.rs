@@ -1,24 +1,60 @@
 use crate::core::mock::Match;
+use crate::error::SpoofError;
 use crate::net::request::Request;
 use http::Method;
+use std::fmt::{Debug, Display};
 
-pub struct MethodMatcher(Method);
+pub struct MethodMatcher(Vec<Method>);
 
 impl MethodMatcher {
     pub fn new<T>(method: T) -> Self
     where
-        T: TryInto<Method>,
-        <T as TryInto<Method>>::Error: std::fmt::Debug,
+        T: TryInto<Method> + Debug,
+        <T as TryInto<Method>>::Error: Display,
     {
-        let method = method
-            .try_into()
-            .expect("Failed to convert to HTTP method.");
-        Self(method)
+        Self::any_of([method])
+    }
+
+    pub fn any_of<T, I>(methods: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: TryInto<Method> + Debug,
+        <T as TryInto<Method>>::Error: Display,
+    {
+        Self::try_any_of(methods).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(method: T) -> Result<Self, SpoofError>
+    where
+        T: TryInto<Method> + Debug,
+        <T as TryInto<Method>>::Error: Display,
+    {
+        Self::try_any_of([method])
+    }
+
+    pub fn try_any_of<T, I>(methods: I) -> Result<Self, SpoofError>
+    where
+        I: IntoIterator<Item = T>,
+        T: TryInto<Method> + Debug,
+        <T as TryInto<Method>>::Error: Display,
+    {
+        methods
+            .into_iter()
+            .map(|method| {
+                let value = format!("{:?}", method);
+                method.try_into().map_err(|e| SpoofError::InvalidMethod {
+                    field: "method".to_string(),
+                    value,
+                    reason: e.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
     }
 }
 
 impl Match for MethodMatcher {
     fn matches(&self, request: &Request) -> bool {
-        request.method == self.0
+        self.0.contains(&request.method)
     }
 }
@@ -1,18 +1,35 @@
 use crate::core::mock::Match;
-use crate::matcher::body::{BodyContainsMatcher, BodyExactMatcher, BodyPartialJsonMatcher};
+use crate::matcher::body::{
+    BodyContainsMatcher, BodyExactMatcher, BodyJsonMatcher, BodyPartialJsonMatcher,
+    BodyRegexMatcher,
+};
+use crate::matcher::client_certificate::ClientCertificateMatcher;
+use crate::matcher::client_ip::ClientIpMatcher;
 use crate::matcher::header::{
-    BasicAuthMatcher, BearerTokenMatcher, HeaderExactMatcher, HeaderExistsMatcher,
-    HeaderValueContainsMatcher, HeaderValueRegexMatcher,
+    BasicAuthMatcher, BearerTokenMatcher, HeaderCaseInsensitiveMatcher, HeaderExactMatcher,
+    HeaderExistsMatcher, HeaderValueContainsMatcher, HeaderValueRegexMatcher,
+    HeaderValueSubstringMatcher,
 };
+use crate::matcher::http_version::HttpVersionMatcher;
+use crate::matcher::listener::ListenerMatcher;
 use crate::matcher::method::MethodMatcher;
-use crate::matcher::path::{PathExactMatcher, PathRegexMatcher};
-use crate::matcher::query::{QueryParamCaseInsensitiveMatcher, QueryParamContainsMatcher, QueryParamExactMatcher, QueryParamExistsMatcher, QueryParamRegexMatcher};
+use crate::matcher::path::{PathExactMatcher, PathRegexMatcher, PathTemplateMatcher};
+use crate::matcher::query::{
+    QueryParamCaseInsensitiveMatcher, QueryParamContainsMatcher, QueryParamExactMatcher,
+    QueryParamExistsMatcher, QueryParamRegexMatcher, QueryParamValuesMatcher,
+};
 use crate::net::request::Request;
-use http::{HeaderName, HeaderValue, Method};
+use http::{HeaderName, HeaderValue, Method, Version};
 use serde::Serialize;
+use std::fmt::{Debug, Display};
 
 pub mod body;
+pub mod client_certificate;
+pub mod client_ip;
 pub mod header;
+pub mod http_version;
+pub mod json;
+pub mod listener;
 pub mod method;
 pub mod path;
 pub mod query;
@@ -27,12 +44,21 @@ impl Match for AnyMatcher {
 
 pub fn method<T>(method: T) -> MethodMatcher
 where
-    T: TryInto<Method>,
-    <T as TryInto<Method>>::Error: std::fmt::Debug,
+    T: TryInto<Method> + Debug,
+    <T as TryInto<Method>>::Error: Display,
 {
     MethodMatcher::new(method)
 }
 
+pub fn methods<T, I>(methods: I) -> MethodMatcher
+where
+    I: IntoIterator<Item = T>,
+    T: TryInto<Method> + Debug,
+    <T as TryInto<Method>>::Error: Display,
+{
+    MethodMatcher::any_of(methods)
+}
+
 pub fn any() -> AnyMatcher {
     AnyMatcher
 }
@@ -51,48 +77,81 @@ where
     PathRegexMatcher::new(path)
 }
 
+pub fn path_template<T>(template: T) -> PathTemplateMatcher
+where
+    T: Into<String>,
+{
+    PathTemplateMatcher::new(template)
+}
+
 pub fn header<K, V>(key: K, value: V) -> HeaderExactMatcher
 where
-    K: TryInto<HeaderName>,
-    <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-    V: TryInto<HeaderValue>,
-    <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+    V: TryInto<HeaderValue> + Debug,
+    <V as TryInto<HeaderValue>>::Error: Display,
 {
     HeaderExactMatcher::new(key, vec![value])
 }
 
 pub fn headers<K, V>(key: K, values: Vec<V>) -> HeaderExactMatcher
 where
-    K: TryInto<HeaderName>,
-    <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-    V: TryInto<HeaderValue>,
-    <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+    V: TryInto<HeaderValue> + Debug,
+    <V as TryInto<HeaderValue>>::Error: Display,
 {
     HeaderExactMatcher::new(key, values)
 }
 
 pub fn header_exists<K>(key: K) -> HeaderExistsMatcher
 where
-    K: TryInto<HeaderName>,
-    <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
 {
     HeaderExistsMatcher::new(key)
 }
 
+pub fn header_is_missing<K>(key: K) -> HeaderExistsMatcher
+where
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+{
+    HeaderExistsMatcher::does_not_exist(key)
+}
+
+pub fn header_case_insensitive<K, V>(key: K, value: V) -> HeaderCaseInsensitiveMatcher
+where
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+    V: Into<String>,
+{
+    HeaderCaseInsensitiveMatcher::new(key, value)
+}
+
+pub fn header_value_contains<K, V>(key: K, part: V) -> HeaderValueSubstringMatcher
+where
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+    V: Into<String>,
+{
+    HeaderValueSubstringMatcher::new(key, part)
+}
+
 pub fn header_regex<K>(key: K, value: &str) -> HeaderValueRegexMatcher
 where
-    K: TryInto<HeaderName>,
-    <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
 {
     HeaderValueRegexMatcher::new(key, value)
 }
 
 pub fn header_contains<K, V>(key: K, values: Vec<V>) -> HeaderValueContainsMatcher
 where
-    K: TryInto<HeaderName>,
-    <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-    V: TryInto<HeaderValue>,
-    <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+    K: TryInto<HeaderName> + Debug,
+    <K as TryInto<HeaderName>>::Error: Display,
+    V: TryInto<HeaderValue> + Debug,
+    <V as TryInto<HeaderValue>>::Error: Display,
 {
     HeaderValueContainsMatcher::new(key, values)
 }
@@ -146,6 +205,27 @@ where
     BodyPartialJsonMatcher::json_string(body)
 }
 
+pub fn body_regex<T>(regex: T) -> BodyRegexMatcher
+where
+    T: AsRef<str>,
+{
+    BodyRegexMatcher::new(regex)
+}
+
+pub fn body_equal_to_json<T>(body: T) -> BodyJsonMatcher
+where
+    T: Serialize,
+{
+    BodyJsonMatcher::json(body)
+}
+
+pub fn body_equal_to_json_string<T>(body: T) -> BodyJsonMatcher
+where
+    T: AsRef<str>,
+{
+    BodyJsonMatcher::json_string(body)
+}
+
 pub fn query_param<K, V>(key: K, value: V) -> QueryParamExactMatcher
 where
     K: Into<String>,
@@ -154,6 +234,15 @@ where
     QueryParamExactMatcher::new(key, value)
 }
 
+pub fn query_param_values<K, I, V>(key: K, values: I) -> QueryParamValuesMatcher
+where
+    K: Into<String>,
+    I: IntoIterator<Item = V>,
+    V: Into<String>,
+{
+    QueryParamValuesMatcher::new(key, values)
+}
+
 pub fn query_param_case_insensitive<K, V>(key: K, value: V) -> QueryParamCaseInsensitiveMatcher
 where
     K: Into<String>,
@@ -186,8 +275,8 @@ where
 }
 
 pub fn query_param_exists<K>(key: K) -> QueryParamExistsMatcher
-    where
-        K: Into<String>,
+where
+    K: Into<String>,
 {
     QueryParamExistsMatcher::does_exist(key)
 }
@@ -206,3 +295,53 @@ where
 {
     BearerTokenMatcher::from_token(token)
 }
+
+pub fn client_certificate_present() -> ClientCertificateMatcher {
+    ClientCertificateMatcher::present()
+}
+
+pub fn client_certificate_subject_matches<T>(regex: T) -> ClientCertificateMatcher
+where
+    T: AsRef<str>,
+{
+    ClientCertificateMatcher::subject_matches(regex)
+}
+
+pub fn client_certificate_issuer_matches<T>(regex: T) -> ClientCertificateMatcher
+where
+    T: AsRef<str>,
+{
+    ClientCertificateMatcher::issuer_matches(regex)
+}
+
+pub fn client_certificate_subject_alt_name<T>(name: T) -> ClientCertificateMatcher
+where
+    T: Into<String>,
+{
+    ClientCertificateMatcher::subject_alt_name(name)
+}
+
+pub fn client_certificate_fingerprint<T>(sha256: T) -> ClientCertificateMatcher
+where
+    T: AsRef<str>,
+{
+    ClientCertificateMatcher::sha256_fingerprint(sha256)
+}
+
+pub fn client_ip<T>(cidr: T) -> ClientIpMatcher
+where
+    T: AsRef<str>,
+{
+    ClientIpMatcher::new(cidr)
+}
+
+pub fn http_version(version: Version) -> HttpVersionMatcher {
+    HttpVersionMatcher::new(version)
+}
+
+pub fn listener<T>(tag: T) -> ListenerMatcher
+where
+    T: Into<String>,
+{
+    ListenerMatcher::new(tag)
+}
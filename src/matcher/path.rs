@@ -1,31 +1,48 @@
 use crate::core::mock::Match;
+use crate::error::SpoofError;
 use crate::net::request::Request;
 use regex::Regex;
+use std::collections::HashMap;
 use url::Url;
 
 pub struct PathExactMatcher(String);
 
 impl PathExactMatcher {
     pub fn new<T>(path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::try_new(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(path: T) -> Result<Self, SpoofError>
     where
         T: Into<String>,
     {
         let path = path.into();
+        let invalid = |reason: String| SpoofError::InvalidPath {
+            field: "path".to_string(),
+            value: format!("{:?}", path),
+            reason,
+        };
 
         if path.contains('?') {
-            panic!("Cannot match path - {} (Path contains '?', use spoof::matcher::query_param to match on query parameters)", path);
+            return Err(invalid(
+                "Path contains '?', use spoof::matcher::query_param to match on query parameters"
+                    .to_string(),
+            ));
         }
 
         if let Ok(url) = Url::parse(&path) {
             if let Some(host) = url.host_str() {
-                panic!("Cannot match path - {} (Path contains host - {}; Spoof is aware of the host, try replacing `path(\"{}\")` with `path(\"{}\")`", path, host, path, url.path());
+                return Err(invalid(format!("Path contains host - {}; Spoof is aware of the host, try replacing `path(\"{}\")` with `path(\"{}\")`", host, path, url.path())));
             }
         }
 
         if path.starts_with('/') {
-            Self(path)
+            Ok(Self(path))
         } else {
-            Self(format!("/{}", path))
+            Ok(Self(format!("/{}", path)))
         }
     }
 }
@@ -40,11 +57,20 @@ pub struct PathRegexMatcher(Regex);
 
 impl PathRegexMatcher {
     pub fn new<T>(path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::try_new(path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(path: T) -> Result<Self, SpoofError>
     where
         T: Into<String>,
     {
         let path = path.into();
-        Self(Regex::new(&path).expect("Failed to create regex from path matcher"))
+        Regex::new(&path)
+            .map(Self)
+            .map_err(|e| SpoofError::regex("path", &path, e))
     }
 }
 
@@ -53,3 +79,68 @@ impl Match for PathRegexMatcher {
         self.0.is_match(request.url.path())
     }
 }
+
+pub struct PathTemplateMatcher {
+    regex: Regex,
+    parameters: Vec<String>,
+}
+
+impl PathTemplateMatcher {
+    pub fn new<T>(template: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::try_new(template).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(template: T) -> Result<Self, SpoofError>
+    where
+        T: Into<String>,
+    {
+        let template = template.into();
+        let template = if template.starts_with('/') {
+            template
+        } else {
+            format!("/{}", template)
+        };
+
+        let mut parameters = Vec::new();
+        let segments = template
+            .split('/')
+            .map(|segment| {
+                match segment
+                    .strip_prefix('{')
+                    .and_then(|it| it.strip_suffix('}'))
+                {
+                    Some(parameter) => {
+                        parameters.push(parameter.to_string());
+                        "([^/]+)".to_string()
+                    }
+                    None => regex::escape(segment),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let regex = Regex::new(&format!("^{}$", segments.join("/")))
+            .map_err(|e| SpoofError::regex("template", &template, e))?;
+
+        Ok(Self { regex, parameters })
+    }
+}
+
+impl Match for PathTemplateMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        self.regex.is_match(request.url.path())
+    }
+
+    fn path_parameters(&self, request: &Request) -> Option<HashMap<String, String>> {
+        let captures = self.regex.captures(request.url.path())?;
+        Some(
+            self.parameters
+                .iter()
+                .zip(captures.iter().skip(1))
+                .filter_map(|(name, value)| Some((name.clone(), value?.as_str().to_string())))
+                .collect(),
+        )
+    }
+}
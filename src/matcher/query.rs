@@ -1,4 +1,5 @@
 use crate::core::mock::Match;
+use crate::error::SpoofError;
 use crate::net::request::Request;
 use regex::Regex;
 
@@ -25,6 +26,34 @@ impl Match for QueryParamExactMatcher {
     }
 }
 
+pub struct QueryParamValuesMatcher(String, Vec<String>);
+
+impl QueryParamValuesMatcher {
+    pub fn new<K, I, V>(key: K, values: I) -> Self
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        let mut values = values.into_iter().map(Into::into).collect::<Vec<_>>();
+        values.sort();
+        Self(key.into(), values)
+    }
+}
+
+impl Match for QueryParamValuesMatcher {
+    fn matches(&self, request: &Request) -> bool {
+        let mut values = request
+            .url
+            .query_pairs()
+            .filter(|(key, _)| key == self.0.as_str())
+            .map(|(_, value)| value.into_owned())
+            .collect::<Vec<_>>();
+        values.sort();
+        values == self.1
+    }
+}
+
 pub struct QueryParamContainsMatcher(String, String);
 
 impl QueryParamContainsMatcher {
@@ -59,8 +88,8 @@ impl QueryParamExistsMatcher {
     }
 
     pub fn does_exist<K>(key: K) -> Self
-        where
-            K: Into<String>,
+    where
+        K: Into<String>,
     {
         Self(key.into(), true)
     }
@@ -76,7 +105,6 @@ impl Match for QueryParamExistsMatcher {
     }
 }
 
-
 pub struct QueryParamCaseInsensitiveMatcher(String, String);
 
 impl QueryParamCaseInsensitiveMatcher {
@@ -104,13 +132,22 @@ pub struct QueryParamRegexMatcher(String, Regex);
 
 impl QueryParamRegexMatcher {
     pub fn new<K, V>(key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self::try_new(key, value).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<K, V>(key: K, value: V) -> Result<Self, SpoofError>
     where
         K: Into<String>,
         V: Into<String>,
     {
         let key = key.into();
-        let regex = Regex::new(&value.into()).expect("Cannot convert field to regex");
-        Self(key, regex)
+        let value = value.into();
+        let regex = Regex::new(&value).map_err(|e| SpoofError::regex("value", &value, e))?;
+        Ok(Self(key, regex))
     }
 }
 
@@ -0,0 +1,186 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::model::mapping::validation::{child_pointer, validate, MappingError};
+use crate::model::mapping::StubMappingStub;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+pub struct LoadedMapping {
+    pub path: PathBuf,
+    pub pointer: String,
+    pub mapping: Result<StubMappingStub, Vec<MappingError>>,
+}
+
+impl LoadedMapping {
+    pub fn location(&self) -> String {
+        if self.pointer.is_empty() {
+            self.path.display().to_string()
+        } else {
+            format!("{}#{}", self.path.display(), self.pointer)
+        }
+    }
+}
+
+pub fn mapping_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|it| it == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+pub fn load_mapping_file(path: &Path) -> Vec<LoadedMapping> {
+    let failed = |message: String| {
+        vec![LoadedMapping {
+            path: path.to_path_buf(),
+            pointer: String::new(),
+            mapping: Err(vec![MappingError {
+                pointer: String::new(),
+                message,
+            }]),
+        }]
+    };
+
+    let content = match std::fs::read(path) {
+        Ok(content) => content,
+        Err(e) => return failed(format!("cannot read file : {}", e)),
+    };
+    let value = match serde_json::from_slice::<Value>(&content) {
+        Ok(value) => value,
+        Err(e) => return failed(format!("invalid JSON : {}", e)),
+    };
+
+    let mappings = match value {
+        Value::Object(mut object) if object.get("mappings").is_some_and(Value::is_array) => {
+            let Some(Value::Array(mappings)) = object.remove("mappings") else {
+                unreachable!()
+            };
+            mappings
+                .into_iter()
+                .enumerate()
+                .map(|(index, mapping)| (child_pointer("/mappings", &index.to_string()), mapping))
+                .collect()
+        }
+        value => vec![(String::new(), value)],
+    };
+
+    mappings
+        .into_iter()
+        .map(|(pointer, value)| LoadedMapping {
+            path: path.to_path_buf(),
+            mapping: parse_mapping(&value, &pointer),
+            pointer,
+        })
+        .collect()
+}
+
+pub fn load_mappings(dir: &Path) -> std::io::Result<Vec<LoadedMapping>> {
+    Ok(mapping_files(dir)?
+        .iter()
+        .flat_map(|path| load_mapping_file(path))
+        .collect())
+}
+
+fn parse_mapping(value: &Value, pointer: &str) -> Result<StubMappingStub, Vec<MappingError>> {
+    let errors = validate(value, pointer);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let mapping = serde_json::from_value(value.clone()).map_err(|e| {
+        vec![MappingError {
+            pointer: pointer.to_string(),
+            message: e.to_string(),
+        }]
+    })?;
+    // Catches what the validator does not know about, such as a regex the engine rejects
+    StubMappingBuilder::try_from(&mapping)
+        .map_err(|e| vec![MappingError::from(e.within(pointer))])?;
+    Ok(mapping)
+}
+
+pub fn check_mapping_id(id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("id must not be empty".to_string());
+    }
+    if id.contains(['/', '\\', '\0']) || id.contains("..") {
+        return Err(format!(
+            "id {:?} must be a plain file name, without /, \\ or ..",
+            id
+        ));
+    }
+    Ok(())
+}
+
+pub fn mapping_file_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    check_mapping_id(id)?;
+    Ok(dir.join(format!("{}.json", id)))
+}
+
+pub fn write_mapping_file(dir: &Path, mapping: &StubMappingStub) -> Result<PathBuf, String> {
+    let id = mapping
+        .id
+        .as_deref()
+        .ok_or_else(|| "Cannot save a mapping without an id".to_string())?;
+    let path = mapping_file_path(dir, id)?;
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {} : {}", dir.display(), e))?;
+
+    let json = serde_json::to_vec_pretty(mapping).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Cannot write {} : {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn mapping_ids_must_be_plain_file_names() {
+        assert!(check_mapping_id("8c5db8b0-2db4-4ad7-a99f-38c9b00da3f7").is_ok());
+        assert!(check_mapping_id("get-users.v2").is_ok());
+
+        for id in ["", "../../escaped", "a/b", "a\\b", "..", "a..b", "nul\0"] {
+            assert!(check_mapping_id(id).is_err(), "{:?} should be rejected", id);
+        }
+    }
+
+    #[test]
+    fn mapping_file_path_stays_in_dir() {
+        let dir = Path::new("/srv/spoof/mappings");
+        assert_eq!(mapping_file_path(dir, "abc").unwrap(), dir.join("abc.json"));
+        assert!(mapping_file_path(dir, "../../escaped").is_err());
+    }
+
+    #[test]
+    fn write_mapping_file_rejects_escaping_ids() {
+        let dir = std::env::temp_dir().join(format!("spoof-{}", uuid::Uuid::new_v4()));
+        let mapping = serde_json::from_value::<StubMappingStub>(json!({
+            "id": "../escaped",
+            "request": {},
+            "response": { "status": 200 }
+        }))
+        .unwrap();
+
+        assert!(write_mapping_file(&dir, &mapping).is_err());
+        assert!(!dir.exists());
+        assert!(!std::env::temp_dir().join("escaped.json").exists());
+    }
+
+    #[test]
+    fn validator_rejects_escaping_ids() {
+        let errors = validate(
+            &json!({ "id": "../../escaped", "request": {}, "response": { "status": 200 } }),
+            "",
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].pointer, "/id");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::core::mock::{Mock, StubMappingBuilder};
+use crate::error::SpoofError;
+use crate::matcher::any;
+use crate::model::request::{MockRegistrable, RequestPatternStub};
+use crate::model::response::definition::ResponseDefinition;
+use crate::model::response::sequence::ResponseSequenceStub;
+use crate::response::response_template::MockResponseBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub mod files;
+pub mod validation;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubMappingStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub request: RequestPatternStub,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<ResponseDefinition>,
+    #[serde(flatten)]
+    pub sequence: ResponseSequenceStub,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+}
+
+impl TryFrom<&StubMappingStub> for StubMappingBuilder {
+    type Error = SpoofError;
+
+    fn try_from(mapping: &StubMappingStub) -> Result<Self, Self::Error> {
+        let mut builder = mapping
+            .request
+            .try_register(Mock::stub_for(any()))
+            .map_err(|e| e.within("/request"))?;
+        if let Some(response) = mapping.response.as_ref() {
+            builder = builder.will_return(
+                MockResponseBuilder::try_from(response).map_err(|e| e.within("/response"))?,
+            )
+        }
+        mapping.sequence.try_register(builder)
+    }
+}
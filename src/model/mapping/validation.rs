@@ -0,0 +1,596 @@
+use crate::error::SpoofError;
+use crate::matcher::client_ip::ClientIpMatcher;
+use crate::matcher::http_version::parse_version;
+use crate::model::mapping::files::check_mapping_id;
+use crate::model::request::body::BodyPatternStub;
+use crate::model::request::client_ip::ClientIpStub;
+use crate::model::request::http_version::VersionStub;
+use crate::model::request::listener::ListenerStub;
+use crate::model::request::method::MethodStub;
+use crate::model::response::transformers::RESPONSE_TEMPLATE;
+use crate::response::fault::Fault;
+use crate::response::files;
+use crate::response::sequence::SequencePolicy;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use http::{HeaderName, HeaderValue, Method, StatusCode};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use url::Url;
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct MappingError {
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Display for MappingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{} : {}", pointer, self.message)
+    }
+}
+
+impl From<SpoofError> for MappingError {
+    fn from(error: SpoofError) -> Self {
+        MappingError {
+            pointer: error.field().to_string(),
+            message: error.message(),
+        }
+    }
+}
+
+pub fn validate(mapping: &Value, pointer: &str) -> Vec<MappingError> {
+    let mut validator = Validator::default();
+    validator.mapping(mapping, pointer);
+    validator.errors
+}
+
+pub(crate) fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[derive(Default)]
+struct Validator {
+    errors: Vec<MappingError>,
+}
+
+impl Validator {
+    fn error<M: Into<String>>(&mut self, pointer: &str, message: M) {
+        self.errors.push(MappingError {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn object<'a>(&mut self, value: &'a Value, pointer: &str) -> Option<&'a Map<String, Value>> {
+        let object = value.as_object();
+        if object.is_none() {
+            self.error(pointer, format!("expected an object, found {}", value));
+        }
+        object
+    }
+
+    fn array<'a>(&mut self, value: &'a Value, pointer: &str) -> Option<&'a Vec<Value>> {
+        let array = value.as_array();
+        if array.is_none() {
+            self.error(pointer, format!("expected an array, found {}", value));
+        }
+        array
+    }
+
+    fn typed<T: DeserializeOwned>(&mut self, value: &Value, pointer: &str) -> Option<T> {
+        match serde_json::from_value(value.clone()) {
+            Ok(typed) => Some(typed),
+            Err(e) => {
+                self.error(pointer, e.to_string());
+                None
+            }
+        }
+    }
+
+    fn regex(&mut self, value: &Value, pointer: &str) {
+        if let Some(regex) = self.typed::<String>(value, pointer) {
+            if let Err(e) = Regex::new(&regex) {
+                // Syntax errors span several lines, the last one names the problem
+                let reason = e.to_string().lines().last().unwrap_or_default().to_string();
+                self.error(pointer, format!("invalid regular expression : {}", reason));
+            }
+        }
+    }
+
+    fn base64(&mut self, value: &Value, pointer: &str) {
+        if let Some(encoded) = self.typed::<String>(value, pointer) {
+            if let Err(e) = BASE64_STANDARD.decode(encoded) {
+                self.error(pointer, format!("invalid base64 : {}", e));
+            }
+        }
+    }
+
+    fn unknown(&mut self, pointer: &str) {
+        self.error(pointer, "unknown field");
+    }
+
+    fn mapping(&mut self, value: &Value, pointer: &str) {
+        let Some(mapping) = self.object(value, pointer) else {
+            return;
+        };
+
+        for (key, value) in mapping {
+            let pointer = child_pointer(pointer, key);
+            match key.as_str() {
+                "id" => {
+                    if let Some(id) = self.typed::<String>(value, &pointer) {
+                        if let Err(e) = check_mapping_id(&id) {
+                            self.error(&pointer, e);
+                        }
+                    }
+                }
+                "name" => {
+                    self.typed::<String>(value, &pointer);
+                }
+                "request" => self.request(value, &pointer),
+                "response" => self.response(value, &pointer, false),
+                "responses" => {
+                    for (index, response) in self
+                        .array(value, &pointer)
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                    {
+                        self.response(response, &child_pointer(&pointer, &index.to_string()), true);
+                    }
+                }
+                "sequencePolicy" => {
+                    self.typed::<SequencePolicy>(value, &pointer);
+                }
+                "persistent" => {
+                    self.typed::<bool>(value, &pointer);
+                }
+                "metadata" => {
+                    self.object(value, &pointer);
+                }
+                _ => self.unknown(&pointer),
+            }
+        }
+    }
+
+    fn request(&mut self, value: &Value, pointer: &str) {
+        let Some(request) = self.object(value, pointer) else {
+            return;
+        };
+
+        for (key, value) in request {
+            let pointer = child_pointer(pointer, key);
+            match key.as_str() {
+                "method" => self.method(value, &pointer),
+                "pathEquals" | "pathTemplate" => {
+                    if let Some(path) = self.typed::<String>(value, &pointer) {
+                        if !path.starts_with('/') {
+                            self.error(&pointer, "path must start with /");
+                        }
+                    }
+                }
+                "pathRegex" => self.regex(value, &pointer),
+                "queryParams" => self.value_matchers(value, &pointer, false),
+                "headers" => self.value_matchers(value, &pointer, true),
+                "bodyPatterns" => {
+                    for (index, pattern) in self
+                        .array(value, &pointer)
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                    {
+                        self.body_pattern(pattern, &child_pointer(&pointer, &index.to_string()));
+                    }
+                }
+                "clientCertificate" => self.client_certificate(value, &pointer),
+                "clientIp" => self.client_ip(value, &pointer),
+                "httpVersion" => self.http_version(value, &pointer),
+                "listener" => {
+                    self.typed::<ListenerStub>(value, &pointer);
+                }
+                _ => self.unknown(&pointer),
+            }
+        }
+    }
+
+    fn client_certificate(&mut self, value: &Value, pointer: &str) {
+        let Some(certificate) = self.object(value, pointer) else {
+            return;
+        };
+
+        for (key, value) in certificate {
+            let pointer = child_pointer(pointer, key);
+            match key.as_str() {
+                "present" => {
+                    self.typed::<bool>(value, &pointer);
+                }
+                "subjectMatches" | "issuerMatches" => self.regex(value, &pointer),
+                "subjectAltName" => {
+                    self.typed::<String>(value, &pointer);
+                }
+                "sha256Fingerprint" => {
+                    if let Some(fingerprint) = self.typed::<String>(value, &pointer) {
+                        let digits = fingerprint.replace(':', "");
+                        if digits.len() != 64 || !digits.chars().all(|it| it.is_ascii_hexdigit()) {
+                            self.error(&pointer, "expected 64 hexadecimal digits");
+                        }
+                    }
+                }
+                _ => self.unknown(&pointer),
+            }
+        }
+    }
+
+    fn client_ip(&mut self, value: &Value, pointer: &str) {
+        let Some(cidrs) = self.typed::<ClientIpStub>(value, pointer) else {
+            return;
+        };
+        for cidr in cidrs.as_slice() {
+            if let Err(e) = ClientIpMatcher::try_new(cidr) {
+                self.error(pointer, e.message());
+            }
+        }
+    }
+
+    fn http_version(&mut self, value: &Value, pointer: &str) {
+        let Some(versions) = self.typed::<VersionStub>(value, pointer) else {
+            return;
+        };
+        for version in versions.as_slice() {
+            if let Err(e) = parse_version(version) {
+                self.error(pointer, e.message());
+            }
+        }
+    }
+
+    fn method(&mut self, value: &Value, pointer: &str) {
+        let methods = match self.typed::<MethodStub>(value, pointer) {
+            Some(MethodStub::Single(method)) => vec![method],
+            Some(MethodStub::Many(methods)) => methods,
+            None => return,
+        };
+        for method in methods {
+            if Method::from_bytes(method.as_bytes()).is_err() {
+                self.error(pointer, format!("invalid HTTP method {}", method));
+            }
+        }
+    }
+
+    fn value_matchers(&mut self, value: &Value, pointer: &str, header_names: bool) {
+        let Some(matchers) = self.object(value, pointer) else {
+            return;
+        };
+
+        for (name, matcher) in matchers {
+            let pointer = child_pointer(pointer, name);
+            if header_names && HeaderName::try_from(name.as_str()).is_err() {
+                self.error(&pointer, format!("invalid header name {}", name));
+            }
+
+            let Some(matcher) = self.object(matcher, &pointer) else {
+                continue;
+            };
+            let mut has_operator = false;
+            for (key, value) in matcher {
+                let pointer = child_pointer(&pointer, key);
+                match key.as_str() {
+                    "equalTo" => {
+                        has_operator = true;
+                        self.equal_to(value, &pointer);
+                    }
+                    "hasExactly" if !header_names => {
+                        has_operator = true;
+                        self.has_exactly(value, &pointer);
+                    }
+                    "contains" => {
+                        has_operator = true;
+                        self.typed::<String>(value, &pointer);
+                    }
+                    "matches" => {
+                        has_operator = true;
+                        self.regex(value, &pointer);
+                    }
+                    "absent" => {
+                        has_operator = true;
+                        self.typed::<bool>(value, &pointer);
+                    }
+                    "caseInsensitive" => {
+                        self.typed::<bool>(value, &pointer);
+                    }
+                    _ => self.unknown(&pointer),
+                }
+            }
+            if !has_operator {
+                self.error(
+                    &pointer,
+                    "expected one of equalTo, contains, matches or absent",
+                );
+            }
+        }
+    }
+
+    fn equal_to(&mut self, value: &Value, pointer: &str) {
+        if !(value.is_string() || value.is_number() || value.is_boolean()) {
+            self.error(
+                pointer,
+                format!("expected a string, number or boolean, found {}", value),
+            );
+        }
+    }
+
+    fn has_exactly(&mut self, value: &Value, pointer: &str) {
+        let Some(items) = self.typed::<Vec<Value>>(value, pointer) else {
+            return;
+        };
+        for (index, item) in items.iter().enumerate() {
+            let pointer = child_pointer(pointer, &index.to_string());
+            let Some(item) = self.object(item, &pointer) else {
+                continue;
+            };
+            if !item.contains_key("equalTo") {
+                self.error(&pointer, "expected equalTo");
+            }
+            for (key, value) in item {
+                let pointer = child_pointer(&pointer, key);
+                match key.as_str() {
+                    "equalTo" => self.equal_to(value, &pointer),
+                    _ => self.unknown(&pointer),
+                }
+            }
+        }
+    }
+
+    fn body_pattern(&mut self, value: &Value, pointer: &str) {
+        let Some(pattern) = self.object(value, pointer) else {
+            return;
+        };
+
+        let mut has_operator = false;
+        for (key, value) in pattern {
+            let pointer = child_pointer(pointer, key);
+            match key.as_str() {
+                "equalTo" | "contains" => {
+                    has_operator = true;
+                    self.typed::<String>(value, &pointer);
+                }
+                "binaryEqualTo" => {
+                    has_operator = true;
+                    self.base64(value, &pointer);
+                }
+                "matches" => {
+                    has_operator = true;
+                    self.regex(value, &pointer);
+                }
+                "equalToJson" => {
+                    has_operator = true;
+                    if let Some(Err(e)) = value.as_str().map(serde_json::from_str::<Value>) {
+                        self.error(&pointer, format!("invalid JSON : {}", e));
+                    }
+                }
+                "ignoreArrayOrder" | "ignoreExtraElements" => {
+                    self.typed::<bool>(value, &pointer);
+                }
+                "numericTolerance" => {
+                    if self
+                        .typed::<f64>(value, &pointer)
+                        .is_some_and(|it| it < 0.0)
+                    {
+                        self.error(&pointer, "tolerance cannot be negative");
+                    }
+                }
+                _ => self.unknown(&pointer),
+            }
+        }
+
+        if !has_operator {
+            self.error(
+                pointer,
+                "expected one of equalTo, binaryEqualTo, contains, matches or equalToJson",
+            );
+        } else {
+            self.typed::<BodyPatternStub>(value, pointer);
+        }
+    }
+
+    fn response(&mut self, value: &Value, pointer: &str, weighted: bool) {
+        let Some(response) = self.object(value, pointer) else {
+            return;
+        };
+
+        for (key, value) in response {
+            let pointer = child_pointer(pointer, key);
+            match key.as_str() {
+                "status" => {
+                    if let Some(status) = self.typed::<u16>(value, &pointer) {
+                        if StatusCode::from_u16(status).is_err() {
+                            self.error(&pointer, format!("invalid status code {}", status));
+                        }
+                    }
+                }
+                "headers" | "additionalProxyRequestHeaders" => self.header_values(value, &pointer),
+                "body" | "proxyUrlPrefixToRemove" => {
+                    self.typed::<String>(value, &pointer);
+                }
+                "jsonBody" => {}
+                "base64Body" => self.base64(value, &pointer),
+                "bodyFileName" => {
+                    if let Some(name) = self.typed::<String>(value, &pointer) {
+                        if files::resolve(Path::new(""), Path::new(&name)).is_none() {
+                            self.error(
+                                &pointer,
+                                "body file must be a relative path inside __files",
+                            );
+                        }
+                    }
+                }
+                "fixedDelayMilliseconds" => {
+                    self.typed::<u64>(value, &pointer);
+                }
+                "fault" => {
+                    self.typed::<Fault>(value, &pointer);
+                }
+                "proxyBaseUrl" => {
+                    if let Some(url) = self.typed::<String>(value, &pointer) {
+                        if let Err(e) = Url::parse(&url) {
+                            self.error(&pointer, format!("invalid URL : {}", e));
+                        }
+                    }
+                }
+                "removeProxyRequestHeaders" => {
+                    for (index, name) in self
+                        .typed::<Vec<String>>(value, &pointer)
+                        .into_iter()
+                        .flatten()
+                        .enumerate()
+                    {
+                        if HeaderName::try_from(name.as_str()).is_err() {
+                            self.error(
+                                &child_pointer(&pointer, &index.to_string()),
+                                format!("invalid header name {}", name),
+                            );
+                        }
+                    }
+                }
+                "transformers" => {
+                    for transformer in self
+                        .typed::<Vec<String>>(value, &pointer)
+                        .into_iter()
+                        .flatten()
+                    {
+                        if transformer != RESPONSE_TEMPLATE {
+                            self.error(&pointer, format!("unknown transformer {}", transformer));
+                        }
+                    }
+                }
+                "weight" if weighted => {
+                    self.typed::<u32>(value, &pointer);
+                }
+                _ => self.unknown(&pointer),
+            }
+        }
+    }
+
+    fn header_values(&mut self, value: &Value, pointer: &str) {
+        let Some(headers) = self.object(value, pointer) else {
+            return;
+        };
+
+        for (name, value) in headers {
+            let pointer = child_pointer(pointer, name);
+            if HeaderName::try_from(name.as_str()).is_err() {
+                self.error(&pointer, format!("invalid header name {}", name));
+            }
+            let values = match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            };
+            if values.iter().any(|it| it.is_object() || it.is_array()) {
+                self.error(&pointer, "expected a value or an array of values");
+                continue;
+            }
+            for value in values {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                if HeaderValue::try_from(value.as_str()).is_err() {
+                    self.error(&pointer, format!("invalid header value {:?}", value));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pointers(mapping: Value, pointer: &str) -> Vec<String> {
+        let mut pointers = validate(&mapping, pointer)
+            .into_iter()
+            .map(|error| error.pointer)
+            .collect::<Vec<_>>();
+        pointers.sort();
+        pointers
+    }
+
+    #[test]
+    fn valid_mapping_has_no_errors() {
+        let mapping = json!({
+            "request": {
+                "method": "GET",
+                "pathRegex": "^/orders/[0-9]+$",
+                "headers": { "Accept": { "equalTo": "application/json" } }
+            },
+            "response": { "status": 200, "jsonBody": { "id": 1 } }
+        });
+        assert!(validate(&mapping, "").is_empty());
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_field() {
+        let mapping = json!({
+            "request": {
+                "pathRegex": "(",
+                "headers": { "X-A": { "matches": "[" } },
+                "unknown": true
+            },
+            "responses": [{ "status": 200 }, { "status": "ok" }]
+        });
+        assert_eq!(
+            pointers(mapping, ""),
+            [
+                "/request/headers/X-A/matches",
+                "/request/pathRegex",
+                "/request/unknown",
+                "/responses/1/status",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_are_located_within_the_given_pointer() {
+        let mapping = json!({ "response": { "status": 1000 } });
+        assert_eq!(
+            pointers(mapping, "/mappings/3"),
+            ["/mappings/3/response/status"]
+        );
+    }
+
+    #[test]
+    fn keys_are_escaped_in_pointers() {
+        let mapping = json!({ "request": { "headers": { "a/b~c": { "equalTo": "x" } } } });
+        assert_eq!(pointers(mapping, ""), ["/request/headers/a~1b~0c"]);
+        assert_eq!(child_pointer("/a", "b/c~d"), "/a/b~1c~0d");
+    }
+
+    #[test]
+    fn has_exactly_is_only_for_query_parameters() {
+        let mapping = json!({
+            "request": {
+                "queryParams": { "tag": { "hasExactly": [{ "equalTo": "a" }, { "contains": "b" }] } },
+                "headers": { "X-Tag": { "hasExactly": [{ "equalTo": "a" }] } }
+            }
+        });
+        assert_eq!(
+            pointers(mapping, ""),
+            [
+                "/request/headers/X-Tag",
+                "/request/headers/X-Tag/hasExactly",
+                "/request/queryParams/tag/hasExactly/1",
+                "/request/queryParams/tag/hasExactly/1/contains",
+            ]
+        );
+    }
+}
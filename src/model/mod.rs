@@ -1 +1,3 @@
+pub mod mapping;
 pub mod request;
+pub mod response;
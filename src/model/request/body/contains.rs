@@ -0,0 +1,17 @@
+use crate::error::SpoofError;
+use crate::matcher::body::BodyContainsMatcher;
+use crate::matcher::body_string_contains;
+use crate::model::request::body::BodyPatternStub;
+
+impl TryFrom<&BodyPatternStub> for BodyContainsMatcher {
+    type Error = SpoofError;
+
+    fn try_from(pattern: &BodyPatternStub) -> Result<Self, Self::Error> {
+        pattern
+            .contains
+            .as_ref()
+            .filter(|_| pattern.is_contains())
+            .map(body_string_contains)
+            .ok_or_else(|| SpoofError::missing("/contains"))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error::SpoofError;
+use crate::matcher::body::BodyExactMatcher;
+use crate::matcher::{body_bytes, body_string};
+use crate::model::request::body::BodyPatternStub;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+
+impl TryFrom<&BodyPatternStub> for BodyExactMatcher {
+    type Error = SpoofError;
+
+    fn try_from(pattern: &BodyPatternStub) -> Result<Self, Self::Error> {
+        if let Some(equal_to) = pattern.equal_to.as_ref() {
+            return Ok(body_string(equal_to));
+        }
+
+        let binary_equal_to = pattern
+            .binary_equal_to
+            .as_ref()
+            .filter(|_| pattern.is_binary_equal_to())
+            .ok_or_else(|| SpoofError::missing("/binaryEqualTo"))?;
+        BASE64_STANDARD
+            .decode(binary_equal_to)
+            .map(body_bytes)
+            .map_err(|e| SpoofError::InvalidBody {
+                field: "/binaryEqualTo".to_string(),
+                reason: e.to_string(),
+            })
+    }
+}
@@ -0,0 +1,35 @@
+use crate::error::SpoofError;
+use crate::matcher::body::BodyJsonMatcher;
+use crate::matcher::json::JsonCompareOptions;
+use crate::model::request::body::BodyPatternStub;
+use serde_json::Value;
+
+impl TryFrom<&BodyPatternStub> for BodyJsonMatcher {
+    type Error = SpoofError;
+
+    fn try_from(pattern: &BodyPatternStub) -> Result<Self, Self::Error> {
+        let json = pattern
+            .equal_to_json
+            .as_ref()
+            .filter(|_| pattern.is_equal_to_json())
+            .map(|it| match it {
+                // `equalToJson` may hold the expected document either inline or as an escaped string
+                Value::String(json) => serde_json::from_str(json).unwrap_or_else(|_| it.clone()),
+                _ => it.clone(),
+            })
+            .ok_or_else(|| SpoofError::missing("/equalToJson"))?;
+        BodyJsonMatcher::try_json(json)
+            .map(|it| it.with_options(JsonCompareOptions::from(pattern)))
+            .map_err(|e| e.at("/equalToJson"))
+    }
+}
+
+impl From<&BodyPatternStub> for JsonCompareOptions {
+    fn from(pattern: &BodyPatternStub) -> Self {
+        JsonCompareOptions {
+            ignore_array_order: pattern.ignore_array_order.unwrap_or_default(),
+            ignore_extra_elements: pattern.ignore_extra_elements.unwrap_or_default(),
+            numeric_tolerance: pattern.numeric_tolerance,
+        }
+    }
+}
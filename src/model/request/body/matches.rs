@@ -0,0 +1,16 @@
+use crate::error::SpoofError;
+use crate::matcher::body::BodyRegexMatcher;
+use crate::model::request::body::BodyPatternStub;
+
+impl TryFrom<&BodyPatternStub> for BodyRegexMatcher {
+    type Error = SpoofError;
+
+    fn try_from(pattern: &BodyPatternStub) -> Result<Self, Self::Error> {
+        let matches = pattern
+            .matches
+            .as_ref()
+            .filter(|_| pattern.is_matches())
+            .ok_or_else(|| SpoofError::missing("/matches"))?;
+        BodyRegexMatcher::try_new(matches).map_err(|e| e.at("/matches"))
+    }
+}
@@ -0,0 +1,107 @@
+mod contains;
+mod exact;
+mod json;
+mod matches;
+
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::body::{
+    BodyContainsMatcher, BodyExactMatcher, BodyJsonMatcher, BodyRegexMatcher,
+};
+use crate::model::mapping::validation::child_pointer;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpBodyPatternsStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_patterns: Option<Vec<BodyPatternStub>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyPatternStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub equal_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary_equal_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub equal_to_json: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_array_order: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_extra_elements: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numeric_tolerance: Option<f64>,
+}
+
+impl BodyPatternStub {
+    pub fn is_equal_to(&self) -> bool {
+        self.equal_to.is_some()
+    }
+
+    pub fn is_binary_equal_to(&self) -> bool {
+        self.binary_equal_to.is_some()
+    }
+
+    pub fn is_contains(&self) -> bool {
+        self.contains.is_some()
+    }
+
+    pub fn is_matches(&self) -> bool {
+        self.matches.is_some()
+    }
+
+    pub fn is_equal_to_json(&self) -> bool {
+        self.equal_to_json.is_some()
+    }
+}
+
+impl Hash for HttpBodyPatternsStub {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Some(patterns) = self.body_patterns.as_ref() {
+            patterns.iter().for_each(|pattern| {
+                serde_json::to_string(pattern)
+                    .unwrap_or_default()
+                    .hash(state)
+            })
+        }
+    }
+}
+
+impl MockRegistrable for HttpBodyPatternsStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        for (index, pattern) in self.body_patterns.iter().flatten().enumerate() {
+            let pointer = child_pointer("/bodyPatterns", &index.to_string());
+            let located = |e: SpoofError| e.within(&pointer);
+            if let Some(exact_matcher) =
+                optional(BodyExactMatcher::try_from(pattern)).map_err(located)?
+            {
+                builder = builder.and(exact_matcher)
+            } else if let Some(contains_matcher) =
+                optional(BodyContainsMatcher::try_from(pattern)).map_err(located)?
+            {
+                builder = builder.and(contains_matcher)
+            } else if let Some(regex_matcher) =
+                optional(BodyRegexMatcher::try_from(pattern)).map_err(located)?
+            {
+                builder = builder.and(regex_matcher)
+            } else if let Some(json_matcher) =
+                optional(BodyJsonMatcher::try_from(pattern)).map_err(located)?
+            {
+                builder = builder.and(json_matcher)
+            }
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::client_certificate::ClientCertificateMatcher;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpClientCertificateStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_certificate: Option<ClientCertificateStub>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCertificateStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub present: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_matches: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer_matches: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_alt_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256_fingerprint: Option<String>,
+}
+
+impl MockRegistrable for HttpClientCertificateStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        for matcher in
+            optional(Vec::<ClientCertificateMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        Ok(builder)
+    }
+}
+
+impl TryFrom<&HttpClientCertificateStub> for Vec<ClientCertificateMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(stub: &HttpClientCertificateStub) -> Result<Self, Self::Error> {
+        let stub = stub
+            .client_certificate
+            .as_ref()
+            .ok_or_else(|| SpoofError::missing("/clientCertificate"))?;
+
+        let mut matchers = Vec::new();
+        match stub.present {
+            Some(true) => matchers.push(ClientCertificateMatcher::present()),
+            Some(false) => matchers.push(ClientCertificateMatcher::absent()),
+            None => {}
+        }
+        if let Some(regex) = stub.subject_matches.as_deref() {
+            matchers.push(
+                ClientCertificateMatcher::try_subject_matches(regex)
+                    .map_err(|e| e.at("/clientCertificate/subjectMatches"))?,
+            )
+        }
+        if let Some(regex) = stub.issuer_matches.as_deref() {
+            matchers.push(
+                ClientCertificateMatcher::try_issuer_matches(regex)
+                    .map_err(|e| e.at("/clientCertificate/issuerMatches"))?,
+            )
+        }
+        if let Some(name) = stub.subject_alt_name.as_ref() {
+            matchers.push(ClientCertificateMatcher::subject_alt_name(name))
+        }
+        if let Some(fingerprint) = stub.sha256_fingerprint.as_ref() {
+            matchers.push(ClientCertificateMatcher::sha256_fingerprint(fingerprint))
+        }
+        Ok(matchers)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::client_ip::ClientIpMatcher;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpClientIpStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<ClientIpStub>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClientIpStub {
+    Single(String),
+    Many(Vec<String>),
+}
+
+impl ClientIpStub {
+    pub(crate) fn as_slice(&self) -> &[String] {
+        match self {
+            ClientIpStub::Single(cidr) => std::slice::from_ref(cidr),
+            ClientIpStub::Many(cidrs) => cidrs,
+        }
+    }
+}
+
+impl MockRegistrable for HttpClientIpStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(client_ip_matcher) = optional(ClientIpMatcher::try_from(self))? {
+            builder = builder.and(client_ip_matcher)
+        }
+        Ok(builder)
+    }
+}
+
+impl TryFrom<&HttpClientIpStub> for ClientIpMatcher {
+    type Error = SpoofError;
+
+    fn try_from(http_client_ip_stub: &HttpClientIpStub) -> Result<Self, Self::Error> {
+        let cidrs = http_client_ip_stub
+            .client_ip
+            .as_ref()
+            .ok_or_else(|| SpoofError::missing("/clientIp"))?
+            .as_slice();
+        ClientIpMatcher::try_any_of(cidrs).map_err(|e| e.at("/clientIp"))
+    }
+}
@@ -0,0 +1,36 @@
+use crate::error::SpoofError;
+use crate::matcher::header::HeaderExistsMatcher;
+use crate::model::request::headers::HttpHeadersStub;
+use crate::model::request::matcher::GenericMatcherStub;
+
+impl TryFrom<&HttpHeadersStub> for Vec<HeaderExistsMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(http_headers: &HttpHeadersStub) -> Result<Self, Self::Error> {
+        http_headers
+            .get_headers_as_iter()
+            .ok_or_else(|| SpoofError::missing("/headers"))?
+            .filter(|it| it.is_absent())
+            .map(|it| HeaderExistsMatcher::try_from(&it))
+            .collect()
+    }
+}
+
+impl TryFrom<&GenericMatcherStub> for HeaderExistsMatcher {
+    type Error = SpoofError;
+
+    fn try_from(header_stub: &GenericMatcherStub) -> Result<Self, Self::Error> {
+        let absent = header_stub
+            .value
+            .as_ref()
+            .filter(|_| header_stub.is_absent())
+            .map(|it| it.absent.unwrap_or_default())
+            .ok_or_else(|| header_stub.missing("/headers", "absent"))?;
+        if absent {
+            HeaderExistsMatcher::try_does_not_exist(header_stub.key.as_str())
+        } else {
+            HeaderExistsMatcher::try_new(header_stub.key.as_str())
+        }
+        .map_err(|e| header_stub.locate(e, "/headers", "absent"))
+    }
+}
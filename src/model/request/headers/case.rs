@@ -0,0 +1,30 @@
+use crate::error::SpoofError;
+use crate::matcher::header::HeaderCaseInsensitiveMatcher;
+use crate::model::request::headers::HttpHeadersStub;
+use crate::model::request::matcher::GenericMatcherStub;
+
+impl TryFrom<&HttpHeadersStub> for Vec<HeaderCaseInsensitiveMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(http_headers: &HttpHeadersStub) -> Result<Self, Self::Error> {
+        http_headers
+            .get_headers_as_iter()
+            .ok_or_else(|| SpoofError::missing("/headers"))?
+            .filter(|it| it.is_case_insensitive())
+            .map(|it| HeaderCaseInsensitiveMatcher::try_from(&it))
+            .collect()
+    }
+}
+
+impl TryFrom<&GenericMatcherStub> for HeaderCaseInsensitiveMatcher {
+    type Error = SpoofError;
+
+    fn try_from(header_stub: &GenericMatcherStub) -> Result<Self, Self::Error> {
+        let equal_to = header_stub
+            .get_equal_to_as_string()
+            .filter(|_| header_stub.is_case_insensitive())
+            .ok_or_else(|| header_stub.missing("/headers", "equalTo"))?;
+        HeaderCaseInsensitiveMatcher::try_new(header_stub.key.as_str(), equal_to)
+            .map_err(|e| header_stub.locate(e, "/headers", "equalTo"))
+    }
+}
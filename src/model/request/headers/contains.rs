@@ -0,0 +1,32 @@
+use crate::error::SpoofError;
+use crate::matcher::header::HeaderValueSubstringMatcher;
+use crate::model::request::headers::HttpHeadersStub;
+use crate::model::request::matcher::GenericMatcherStub;
+
+impl TryFrom<&HttpHeadersStub> for Vec<HeaderValueSubstringMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(http_headers: &HttpHeadersStub) -> Result<Self, Self::Error> {
+        http_headers
+            .get_headers_as_iter()
+            .ok_or_else(|| SpoofError::missing("/headers"))?
+            .filter(|it| it.is_contains())
+            .map(|it| HeaderValueSubstringMatcher::try_from(&it))
+            .collect()
+    }
+}
+
+impl TryFrom<&GenericMatcherStub> for HeaderValueSubstringMatcher {
+    type Error = SpoofError;
+
+    fn try_from(header_stub: &GenericMatcherStub) -> Result<Self, Self::Error> {
+        let contains = header_stub
+            .value
+            .as_ref()
+            .filter(|_| header_stub.is_contains())
+            .and_then(|it| it.contains.as_ref())
+            .ok_or_else(|| header_stub.missing("/headers", "contains"))?;
+        HeaderValueSubstringMatcher::try_new(header_stub.key.as_str(), contains)
+            .map_err(|e| header_stub.locate(e, "/headers", "contains"))
+    }
+}
@@ -0,0 +1,104 @@
+use crate::error::SpoofError;
+use crate::matcher::header::HeaderExactMatcher;
+use crate::model::request::headers::HttpHeadersStub;
+use crate::model::request::matcher::GenericMatcherStub;
+
+impl TryFrom<&HttpHeadersStub> for Vec<HeaderExactMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(http_headers: &HttpHeadersStub) -> Result<Self, Self::Error> {
+        http_headers
+            .get_headers_as_iter()
+            .ok_or_else(|| SpoofError::missing("/headers"))?
+            .filter(|it| it.is_exact_match())
+            .map(|it| HeaderExactMatcher::try_from(&it))
+            .collect()
+    }
+}
+
+impl TryFrom<&GenericMatcherStub> for HeaderExactMatcher {
+    type Error = SpoofError;
+
+    fn try_from(header_stub: &GenericMatcherStub) -> Result<Self, Self::Error> {
+        let equal_to = header_stub
+            .get_equal_to_as_string()
+            .ok_or_else(|| header_stub.missing("/headers", "equalTo"))?;
+        // Request values are compared item by item, a recorded `a, b` must be too
+        let values = equal_to.split(',').map(str::trim).collect();
+        HeaderExactMatcher::try_new(header_stub.key.as_str(), values)
+            .map_err(|e| header_stub.locate(e, "/headers", "equalTo"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mock::{Match, Mock};
+    use crate::matcher::any;
+    use crate::model::request::MockRegistrable;
+    use crate::net::request::{BodyStatus, Request};
+    use http::{HeaderMap, Method, Version};
+    use serde_json::json;
+
+    fn request(accept: &str) -> Request {
+        let mut headers = HeaderMap::new();
+        headers.insert("accept", accept.parse().unwrap());
+        Request {
+            url: "http://localhost/".parse().unwrap(),
+            method: Method::GET,
+            version: Version::HTTP_11,
+            headers,
+            body: Vec::new(),
+            raw_body: Vec::new(),
+            client_certificate: None,
+            body_status: BodyStatus::Complete,
+            remote_address: None,
+            client_ip: None,
+            listener: None,
+        }
+    }
+
+    fn matcher(equal_to: &str) -> HeaderExactMatcher {
+        let stub: HttpHeadersStub =
+            serde_json::from_value(json!({ "headers": { "Accept": { "equalTo": equal_to } } }))
+                .unwrap();
+        Vec::<HeaderExactMatcher>::try_from(&stub)
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn equal_to_matches_a_whole_list_value() {
+        let matcher = matcher("text/html, application/json");
+        assert!(matcher.matches(&request("text/html, application/json")));
+        assert!(matcher.matches(&request("text/html,application/json")));
+        assert!(!matcher.matches(&request("text/html")));
+    }
+
+    #[test]
+    fn equal_to_matches_a_single_value() {
+        let matcher = matcher("text/html");
+        assert!(matcher.matches(&request("text/html")));
+        assert!(!matcher.matches(&request("text/html, application/json")));
+    }
+
+    #[test]
+    fn unusable_matchers_are_rejected_instead_of_ignored() {
+        for headers in [
+            json!({ "Accept": { "equalTo": null } }),
+            json!({ "Accept": { "equalTo": ["text/html"] } }),
+            json!({ "Accept": { "hasExactly": [{ "equalTo": "text/html" }] } }),
+            json!({ "Accept": "text/html" }),
+        ] {
+            let stub: HttpHeadersStub =
+                serde_json::from_value(json!({ "headers": headers })).unwrap();
+            let builder = Mock::stub_for(any());
+            assert!(
+                stub.try_register(builder).is_err(),
+                "{} was accepted",
+                headers
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::error::SpoofError;
+use crate::matcher::header::HeaderValueRegexMatcher;
+use crate::model::request::headers::HttpHeadersStub;
+use crate::model::request::matcher::GenericMatcherStub;
+
+impl TryFrom<&HttpHeadersStub> for Vec<HeaderValueRegexMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(http_headers: &HttpHeadersStub) -> Result<Self, Self::Error> {
+        http_headers
+            .get_headers_as_iter()
+            .ok_or_else(|| SpoofError::missing("/headers"))?
+            .filter(|it| it.is_by_regex())
+            .map(|it| HeaderValueRegexMatcher::try_from(&it))
+            .collect()
+    }
+}
+
+impl TryFrom<&GenericMatcherStub> for HeaderValueRegexMatcher {
+    type Error = SpoofError;
+
+    fn try_from(header_stub: &GenericMatcherStub) -> Result<Self, Self::Error> {
+        let matches = header_stub
+            .get_matches_as_str()
+            .filter(|_| header_stub.is_by_regex())
+            .ok_or_else(|| header_stub.missing("/headers", "matches"))?;
+        HeaderValueRegexMatcher::try_new(header_stub.key.as_str(), matches)
+            .map_err(|e| header_stub.locate(e, "/headers", "matches"))
+    }
+}
@@ -0,0 +1,82 @@
+mod absent;
+mod case;
+mod contains;
+mod exact;
+mod matches;
+
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::header::{
+    HeaderCaseInsensitiveMatcher, HeaderExactMatcher, HeaderExistsMatcher, HeaderValueRegexMatcher,
+    HeaderValueSubstringMatcher,
+};
+use crate::model::request::matcher::GenericMatcherStub;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, Default, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpHeadersStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Map<String, Value>>,
+}
+
+impl HttpHeadersStub {
+    fn get_headers_as_iter(&self) -> Option<impl Iterator<Item = GenericMatcherStub> + '_> {
+        self.headers.as_ref().map(|h| {
+            h.iter()
+                .filter_map(|it| GenericMatcherStub::try_from(it).ok())
+        })
+    }
+}
+
+impl PartialEq for HttpHeadersStub {
+    fn eq(&self, other: &Self) -> bool {
+        self.headers.as_ref().eq(&other.headers.as_ref())
+    }
+}
+
+impl Hash for HttpHeadersStub {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if let Some(map) = self.headers.as_ref() {
+            map.iter().for_each(|(key, value)| {
+                key.hash(state);
+                value.to_string().hash(state);
+            })
+        }
+    }
+}
+
+impl MockRegistrable for HttpHeadersStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(matchers) = self.headers.as_ref() {
+            GenericMatcherStub::check_all("/headers", matchers, false)?;
+        }
+        for matcher in optional(Vec::<HeaderExactMatcher>::try_from(self))?.unwrap_or_default() {
+            builder = builder.and(matcher)
+        }
+        for matcher in
+            optional(Vec::<HeaderCaseInsensitiveMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in
+            optional(Vec::<HeaderValueSubstringMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in optional(Vec::<HeaderValueRegexMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in optional(Vec::<HeaderExistsMatcher>::try_from(self))?.unwrap_or_default() {
+            builder = builder.and(matcher)
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::http_version::HttpVersionMatcher;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpVersionStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_version: Option<VersionStub>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VersionStub {
+    Single(String),
+    Many(Vec<String>),
+}
+
+impl VersionStub {
+    pub(crate) fn as_slice(&self) -> &[String] {
+        match self {
+            VersionStub::Single(version) => std::slice::from_ref(version),
+            VersionStub::Many(versions) => versions,
+        }
+    }
+}
+
+impl MockRegistrable for HttpVersionStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(version_matcher) = optional(HttpVersionMatcher::try_from(self))? {
+            builder = builder.and(version_matcher)
+        }
+        Ok(builder)
+    }
+}
+
+impl TryFrom<&HttpVersionStub> for HttpVersionMatcher {
+    type Error = SpoofError;
+
+    fn try_from(http_version_stub: &HttpVersionStub) -> Result<Self, Self::Error> {
+        let versions = http_version_stub
+            .http_version
+            .as_ref()
+            .ok_or_else(|| SpoofError::missing("/httpVersion"))?
+            .as_slice();
+        HttpVersionMatcher::try_parse_any_of(versions).map_err(|e| e.at("/httpVersion"))
+    }
+}
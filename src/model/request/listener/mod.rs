@@ -0,0 +1,53 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::listener::ListenerMatcher;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpListenerStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listener: Option<ListenerStub>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ListenerStub {
+    Single(String),
+    Many(Vec<String>),
+}
+
+impl ListenerStub {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            ListenerStub::Single(tag) => std::slice::from_ref(tag),
+            ListenerStub::Many(tags) => tags,
+        }
+    }
+}
+
+impl MockRegistrable for HttpListenerStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(listener_matcher) = optional(ListenerMatcher::try_from(self))? {
+            builder = builder.and(listener_matcher)
+        }
+        Ok(builder)
+    }
+}
+
+impl TryFrom<&HttpListenerStub> for ListenerMatcher {
+    type Error = SpoofError;
+
+    fn try_from(http_listener_stub: &HttpListenerStub) -> Result<Self, Self::Error> {
+        let tags = http_listener_stub
+            .listener
+            .as_ref()
+            .ok_or_else(|| SpoofError::missing("/listener"))?
+            .as_slice();
+        Ok(ListenerMatcher::any_of(tags.iter().cloned()))
+    }
+}
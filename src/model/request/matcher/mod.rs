@@ -1,7 +1,8 @@
-use regex::Regex;
+use crate::error::SpoofError;
+use crate::model::mapping::validation::child_pointer;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::{ops::Not, str::FromStr};
+use serde_json::{json, Map, Value};
+use std::ops::Not;
 
 pub struct GenericMatcherStub {
     pub key: String,
@@ -21,6 +22,8 @@ pub struct GenericMatcherValueStruct {
     pub matches: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub absent: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_exactly: Option<Vec<GenericMatcherValueStruct>>,
 }
 
 impl GenericMatcherStub {
@@ -61,27 +64,33 @@ impl GenericMatcherStub {
             .unwrap_or_default()
     }
 
+    pub fn is_has_exactly(&self) -> bool {
+        self.value
+            .as_ref()
+            .and_then(|value| value.has_exactly.as_ref())
+            .is_some()
+    }
+
     pub fn is_exact_match(&self) -> bool {
         self.is_equal_to() && !self.is_case_insensitive() && !self.is_contains()
     }
 
     pub fn get_equal_to_as_string(&self) -> Option<String> {
-        self.value.as_ref()?.equal_to.as_ref().and_then(|value| {
-            value
-                .as_str()
-                .map(ToString::to_string)
-                .or_else(|| value.as_bool().map(|v| v.to_string()))
-                .or_else(|| value.as_i64().map(|v| v.to_string()))
-        })
+        self.value.as_ref()?.equal_to.as_ref().and_then(as_string)
     }
 
-    pub fn get_matches_as_str(&self) -> Option<&str> {
-        self.value.as_ref()?.matches.as_ref()?.as_str()
+    pub fn get_has_exactly_as_strings(&self) -> Option<Vec<String>> {
+        self.value
+            .as_ref()?
+            .has_exactly
+            .as_ref()?
+            .iter()
+            .map(|item| item.equal_to.as_ref().and_then(as_string))
+            .collect()
     }
 
-    pub fn get_matches_as_regex(&self) -> Option<Regex> {
-        self.get_matches_as_str()
-            .and_then(|it| Regex::from_str(it).ok())
+    pub fn get_matches_as_str(&self) -> Option<&str> {
+        self.value.as_ref()?.matches.as_ref()?.as_str()
     }
 
     pub fn is_by_regex(&self) -> bool {
@@ -92,8 +101,91 @@ impl GenericMatcherStub {
     }
 }
 
+fn as_string(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(ToString::to_string)
+        .or_else(|| value.as_bool().map(|v| v.to_string()))
+        .or_else(|| value.as_number().map(|v| v.to_string()))
+}
+
+impl GenericMatcherStub {
+    pub(crate) fn locate(&self, error: SpoofError, parent: &str, attribute: &str) -> SpoofError {
+        let key = child_pointer(parent, &self.key);
+        match error {
+            SpoofError::InvalidHeaderName { .. } => error.at(key),
+            error => error.at(child_pointer(&key, attribute)),
+        }
+    }
+
+    pub(crate) fn missing(&self, parent: &str, attribute: &str) -> SpoofError {
+        SpoofError::missing(&child_pointer(&child_pointer(parent, &self.key), attribute))
+    }
+}
+
+impl GenericMatcherStub {
+    pub(crate) fn check_all(
+        parent: &str,
+        matchers: &Map<String, Value>,
+        has_exactly: bool,
+    ) -> Result<(), SpoofError> {
+        for (key, value) in matchers {
+            let pointer = child_pointer(parent, key);
+            let invalid = |pointer: &str, value: &Value, reason: &str| SpoofError::InvalidMatcher {
+                field: pointer.to_string(),
+                value: value.to_string(),
+                reason: reason.to_string(),
+            };
+            let matcher = serde_json::from_value::<GenericMatcherValueStruct>(value.clone())
+                .map_err(|e| invalid(&pointer, value, &e.to_string()))?;
+
+            // `null` deserializes as no `equalTo` at all
+            let equal_to = value.get("equalTo");
+            if let Some(equal_to) = equal_to.filter(|it| as_string(it).is_none()) {
+                let pointer = child_pointer(&pointer, "equalTo");
+                return Err(invalid(
+                    &pointer,
+                    equal_to,
+                    "expected a string, number or boolean",
+                ));
+            }
+            if let Some(matches) = matcher.matches.as_ref().filter(|it| !it.is_string()) {
+                let pointer = child_pointer(&pointer, "matches");
+                return Err(invalid(&pointer, matches, "expected a string"));
+            }
+            if let Some(values) = matcher.has_exactly.as_ref() {
+                let pointer = child_pointer(&pointer, "hasExactly");
+                if !has_exactly {
+                    return Err(invalid(&pointer, value, "only query parameters support it"));
+                }
+                for (index, item) in values.iter().enumerate() {
+                    if item.equal_to.as_ref().and_then(as_string).is_none() {
+                        let pointer = child_pointer(&pointer, &index.to_string());
+                        let item = json!(item);
+                        return Err(invalid(&pointer, &item, "expected an equalTo string"));
+                    }
+                }
+            }
+
+            let has_operator = equal_to.is_some()
+                || matcher.contains.is_some()
+                || matcher.matches.is_some()
+                || matcher.absent.is_some()
+                || matcher.has_exactly.is_some();
+            if !has_operator {
+                return Err(invalid(
+                    &pointer,
+                    value,
+                    "expected one of equalTo, contains, matches or absent",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl TryFrom<(&String, &Value)> for GenericMatcherStub {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from((k, v): (&String, &Value)) -> Result<Self, Self::Error> {
         Ok(Self {
@@ -0,0 +1,75 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::matcher::method::MethodMatcher;
+use crate::model::request::{optional, MockRegistrable};
+use serde::{Deserialize, Serialize};
+
+const ANY_METHOD: &str = "ANY";
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpMethodStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<MethodStub>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MethodStub {
+    Single(String),
+    Many(Vec<String>),
+}
+
+impl MethodStub {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            MethodStub::Single(method) => std::slice::from_ref(method),
+            MethodStub::Many(methods) => methods,
+        }
+    }
+}
+
+impl HttpMethodStub {
+    fn is_any(&self) -> bool {
+        self.method
+            .as_ref()
+            .map(|it| {
+                it.as_slice()
+                    .iter()
+                    .any(|method| method.eq_ignore_ascii_case(ANY_METHOD))
+            })
+            .unwrap_or(true)
+    }
+}
+
+impl MockRegistrable for HttpMethodStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(method_matcher) = optional(MethodMatcher::try_from(self))? {
+            builder = builder.and(method_matcher)
+        }
+        Ok(builder)
+    }
+}
+
+impl TryFrom<&HttpMethodStub> for MethodMatcher {
+    type Error = SpoofError;
+
+    fn try_from(http_method_stub: &HttpMethodStub) -> Result<Self, Self::Error> {
+        if http_method_stub.is_any() {
+            return Err(SpoofError::missing("/method"));
+        }
+
+        let methods = http_method_stub
+            .method
+            .as_ref()
+            .ok_or_else(|| SpoofError::missing("/method"))?
+            .as_slice()
+            .iter()
+            .map(|method| method.to_uppercase())
+            .collect::<Vec<_>>();
+        MethodMatcher::try_any_of(methods.iter().map(String::as_str)).map_err(|e| e.at("/method"))
+    }
+}
@@ -1,9 +1,80 @@
 use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::model::request::body::HttpBodyPatternsStub;
+use crate::model::request::client_certificate::HttpClientCertificateStub;
+use crate::model::request::client_ip::HttpClientIpStub;
+use crate::model::request::headers::HttpHeadersStub;
+use crate::model::request::http_version::HttpVersionStub;
+use crate::model::request::listener::HttpListenerStub;
+use crate::model::request::method::HttpMethodStub;
+use crate::model::request::path::HttpPathStub;
+use crate::model::request::query::HttpQueryParamsStub;
+use serde::{Deserialize, Serialize};
 
+pub mod body;
+pub mod client_certificate;
+pub mod client_ip;
+pub mod headers;
+pub mod http_version;
+pub mod listener;
 mod matcher;
+pub mod method;
 pub mod path;
-mod query;
+pub mod query;
 
 pub trait MockRegistrable {
-    fn register(&self, builder: StubMappingBuilder) -> StubMappingBuilder;
+    fn try_register(&self, builder: StubMappingBuilder) -> Result<StubMappingBuilder, SpoofError>;
+
+    fn register(&self, builder: StubMappingBuilder) -> StubMappingBuilder {
+        self.try_register(builder)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+pub(crate) fn optional<T>(result: Result<T, SpoofError>) -> Result<Option<T>, SpoofError> {
+    match result {
+        Ok(it) => Ok(Some(it)),
+        Err(e) if e.is_missing() => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPatternStub {
+    #[serde(flatten)]
+    pub method: HttpMethodStub,
+    #[serde(flatten)]
+    pub path: HttpPathStub,
+    #[serde(flatten)]
+    pub query: HttpQueryParamsStub,
+    #[serde(flatten)]
+    pub headers: HttpHeadersStub,
+    #[serde(flatten)]
+    pub body: HttpBodyPatternsStub,
+    #[serde(flatten)]
+    pub client_certificate: HttpClientCertificateStub,
+    #[serde(flatten)]
+    pub client_ip: HttpClientIpStub,
+    #[serde(flatten)]
+    pub http_version: HttpVersionStub,
+    #[serde(flatten)]
+    pub listener: HttpListenerStub,
+}
+
+impl MockRegistrable for RequestPatternStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        builder = self.method.try_register(builder)?;
+        builder = self.path.try_register(builder)?;
+        builder = self.query.try_register(builder)?;
+        builder = self.headers.try_register(builder)?;
+        builder = self.body.try_register(builder)?;
+        builder = self.client_certificate.try_register(builder)?;
+        builder = self.client_ip.try_register(builder)?;
+        builder = self.http_version.try_register(builder)?;
+        self.listener.try_register(builder)
+    }
 }
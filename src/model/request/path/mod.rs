@@ -1,7 +1,7 @@
 use crate::core::mock::StubMappingBuilder;
-use crate::matcher::path::{PathExactMatcher, PathRegexMatcher};
-use crate::matcher::{path, path_regex};
-use crate::model::request::MockRegistrable;
+use crate::error::SpoofError;
+use crate::matcher::path::{PathExactMatcher, PathRegexMatcher, PathTemplateMatcher};
+use crate::model::request::{optional, MockRegistrable};
 use url::Url;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
@@ -11,52 +11,94 @@ pub struct HttpPathStub {
     pub path_equals: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path_regex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_template: Option<String>,
 }
 
 impl MockRegistrable for HttpPathStub {
-    fn register(&self, mut builder: StubMappingBuilder) -> StubMappingBuilder {
-        if let Ok(path_matcher) = PathExactMatcher::try_from(self) {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(path_matcher) = optional(PathExactMatcher::try_from(self))? {
             builder = builder.and(path_matcher)
-        } else if let Ok(path_regex_matcher) = PathRegexMatcher::try_from(self) {
+        } else if let Some(path_regex_matcher) = optional(PathRegexMatcher::try_from(self))? {
             builder = builder.and(path_regex_matcher)
+        } else if let Some(path_template_matcher) = optional(PathTemplateMatcher::try_from(self))? {
+            builder = builder.and(path_template_matcher)
         }
-        builder
+        Ok(builder)
     }
 }
 
 impl TryFrom<&HttpPathStub> for PathExactMatcher {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(http_path_stub: &HttpPathStub) -> Result<Self, Self::Error> {
-        Url::try_from(http_path_stub).map(|url| path(url.path()))
+        let url = Url::try_from(http_path_stub)?;
+        PathExactMatcher::try_new(url.path()).map_err(|e| e.at("/pathEquals"))
     }
 }
 
 impl TryFrom<&HttpPathStub> for Url {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(http_path_stub: &HttpPathStub) -> Result<Self, Self::Error> {
-        http_path_stub
+        let path = http_path_stub
             .path_equals
             .as_ref()
-            .map(|it| format!("http:://localhost{it}"))
-            .map(|it| Url::parse(&it))
-            .map(|url| match url {
-                Ok(url) => Ok(url),
-                Err(_) => Err(()),
-            })
-            .unwrap_or(Err(()))
+            .ok_or_else(|| SpoofError::missing("/pathEquals"))?;
+        Url::parse(&format!("http://localhost{path}")).map_err(|e| SpoofError::InvalidPath {
+            field: "/pathEquals".to_string(),
+            value: format!("{:?}", path),
+            reason: e.to_string(),
+        })
     }
 }
 
 impl TryFrom<&HttpPathStub> for PathRegexMatcher {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(http_path_stub: &HttpPathStub) -> Result<Self, Self::Error> {
-        http_path_stub
+        let path_regex = http_path_stub
             .path_regex
             .as_deref()
-            .map(path_regex)
-            .ok_or_else(|| ())
+            .ok_or_else(|| SpoofError::missing("/pathRegex"))?;
+        PathRegexMatcher::try_new(path_regex).map_err(|e| e.at("/pathRegex"))
+    }
+}
+
+impl TryFrom<&HttpPathStub> for PathTemplateMatcher {
+    type Error = SpoofError;
+
+    fn try_from(http_path_stub: &HttpPathStub) -> Result<Self, Self::Error> {
+        let path_template = http_path_stub
+            .path_template
+            .as_deref()
+            .ok_or_else(|| SpoofError::missing("/pathTemplate"))?;
+        PathTemplateMatcher::try_new(path_template).map_err(|e| e.at("/pathTemplate"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mock::Mock;
+    use crate::net::request::Request;
+    use serde_json::json;
+
+    fn mock(request: serde_json::Value) -> Mock {
+        let mapping = json!({ "request": request, "response": { "status": 200 } });
+        Mock::try_from_mapping(serde_json::from_value(mapping).unwrap()).unwrap()
+    }
+
+    // The base URL used to be `http:://localhost`, which failed to parse and left the stub
+    // without any path matcher, so it matched every path
+    #[test]
+    fn path_equals_matches_only_its_path() {
+        let mock = mock(json!({ "pathEquals": "/orders/1" }));
+        assert!(mock.matches(&Request::get("http://localhost/orders/1")));
+        assert!(mock.matches(&Request::get("http://localhost/orders/1?page=2")));
+        assert!(!mock.matches(&Request::get("http://localhost/orders/2")));
+        assert!(!mock.matches(&Request::get("http://localhost/")));
     }
 }
@@ -1,25 +1,23 @@
+use crate::error::SpoofError;
 use crate::matcher::query::QueryParamExistsMatcher;
 use crate::model::request::matcher::GenericMatcherStub;
 use crate::model::request::query::HttpQueryParamsStub;
-use itertools::Itertools;
 
 impl TryFrom<&HttpQueryParamsStub> for Vec<QueryParamExistsMatcher> {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(http_query_params: &HttpQueryParamsStub) -> Result<Self, Self::Error> {
         http_query_params
             .get_query_params_as_iter()
-            .ok_or_else(|| ())
-            .map(|iter| {
-                iter.filter(|it| it.is_absent())
-                    .filter_map(|it| QueryParamExistsMatcher::try_from(&it).ok())
-                    .collect_vec()
-            })
+            .ok_or_else(|| SpoofError::missing("/queryParams"))?
+            .filter(|it| it.is_absent())
+            .map(|it| QueryParamExistsMatcher::try_from(&it))
+            .collect()
     }
 }
 
 impl TryFrom<&GenericMatcherStub> for QueryParamExistsMatcher {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(query: &GenericMatcherStub) -> Result<Self, Self::Error> {
         query
@@ -29,11 +27,11 @@ impl TryFrom<&GenericMatcherStub> for QueryParamExistsMatcher {
             .map(|it| it.absent.unwrap_or_default())
             .map(|absent| {
                 if absent {
-                    QueryParamExistsMatcher::does_not_exist(query.key.to_string())
+                    QueryParamExistsMatcher::does_not_exist(query.key.as_str())
                 } else {
-                    QueryParamExistsMatcher::does_exist(query.key.to_string())
+                    QueryParamExistsMatcher::does_exist(query.key.as_str())
                 }
             })
-            .ok_or_else(|| ())
+            .ok_or_else(|| query.missing("/queryParams", "absent"))
     }
 }
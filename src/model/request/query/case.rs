@@ -1,32 +1,30 @@
+use crate::error::SpoofError;
 use crate::matcher::query::QueryParamCaseInsensitiveMatcher;
 use crate::matcher::query_param_case_insensitive;
 use crate::model::request::matcher::GenericMatcherStub;
 use crate::model::request::query::HttpQueryParamsStub;
-use itertools::Itertools;
 
 impl TryFrom<&HttpQueryParamsStub> for Vec<QueryParamCaseInsensitiveMatcher> {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(http_query_params: &HttpQueryParamsStub) -> Result<Self, Self::Error> {
         http_query_params
             .get_query_params_as_iter()
-            .ok_or_else(|| ())
-            .map(|iter| {
-                iter.filter(|it| it.is_case_insensitive())
-                    .filter_map(|it| QueryParamCaseInsensitiveMatcher::try_from(&it).ok())
-                    .collect_vec()
-            })
+            .ok_or_else(|| SpoofError::missing("/queryParams"))?
+            .filter(|it| it.is_case_insensitive())
+            .map(|it| QueryParamCaseInsensitiveMatcher::try_from(&it))
+            .collect()
     }
 }
 
 impl TryFrom<&GenericMatcherStub> for QueryParamCaseInsensitiveMatcher {
-    type Error = ();
+    type Error = SpoofError;
 
     fn try_from(query: &GenericMatcherStub) -> Result<Self, Self::Error> {
         query
             .get_equal_to_as_string()
             .filter(|_| query.is_case_insensitive())
             .map(|equal| query_param_case_insensitive(query.key.as_str(), equal))
-            .ok_or_else(|| ())
+            .ok_or_else(|| query.missing("/queryParams", "equalTo"))
     }
 }
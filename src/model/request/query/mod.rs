@@ -3,14 +3,16 @@ mod case;
 mod contains;
 mod exact;
 mod matches;
+mod values;
 
 use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
 use crate::matcher::query::{
     QueryParamCaseInsensitiveMatcher, QueryParamContainsMatcher, QueryParamExactMatcher,
-    QueryParamExistsMatcher, QueryParamRegexMatcher,
+    QueryParamExistsMatcher, QueryParamRegexMatcher, QueryParamValuesMatcher,
 };
 use crate::model::request::matcher::GenericMatcherStub;
-use crate::model::request::MockRegistrable;
+use crate::model::request::{optional, MockRegistrable};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::hash::{Hash, Hasher};
@@ -49,30 +51,117 @@ impl Hash for HttpQueryParamsStub {
 }
 
 impl MockRegistrable for HttpQueryParamsStub {
-    fn register(&self, mut builder: StubMappingBuilder) -> StubMappingBuilder {
-        if let Ok(equals_matcher) = Vec::<QueryParamExactMatcher>::try_from(self) {
-            for matcher in equals_matcher {
-                builder = builder.and(matcher)
-            }
-        } else if let Ok(case_insensitive_matchers) =
-            Vec::<QueryParamCaseInsensitiveMatcher>::try_from(self)
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(matchers) = self.query_params.as_ref() {
+            GenericMatcherStub::check_all("/queryParams", matchers, true)?;
+        }
+        for matcher in optional(Vec::<QueryParamExactMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in
+            optional(Vec::<QueryParamCaseInsensitiveMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in
+            optional(Vec::<QueryParamContainsMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in optional(Vec::<QueryParamRegexMatcher>::try_from(self))?.unwrap_or_default()
         {
-            for matcher in case_insensitive_matchers {
-                builder = builder.and(matcher)
-            }
-        } else if let Ok(contains_matchers) = Vec::<QueryParamContainsMatcher>::try_from(self) {
-            for matcher in contains_matchers {
-                builder = builder.and(matcher)
-            }
-        } else if let Ok(regex_matchers) = Vec::<QueryParamRegexMatcher>::try_from(self) {
-            for matcher in regex_matchers {
-                builder = builder.and(matcher)
-            }
-        } else if let Ok(exists_matchers) = Vec::<QueryParamExistsMatcher>::try_from(self) {
-            for matcher in exists_matchers {
-                builder = builder.and(matcher)
-            }
+            builder = builder.and(matcher)
+        }
+        for matcher in optional(Vec::<QueryParamExistsMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        for matcher in optional(Vec::<QueryParamValuesMatcher>::try_from(self))?.unwrap_or_default()
+        {
+            builder = builder.and(matcher)
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mock::Mock;
+    use crate::error::SpoofError;
+    use crate::net::request::Request;
+    use serde_json::json;
+
+    fn mock(query_params: serde_json::Value) -> Mock {
+        let mapping = json!({
+            "request": { "queryParams": query_params },
+            "response": { "status": 200 }
+        });
+        Mock::try_from_mapping(serde_json::from_value(mapping).unwrap()).unwrap()
+    }
+
+    // Matcher kinds used to be registered in an `else if` chain, so only the first kind present
+    // was checked and `b` below was ignored
+    #[test]
+    fn every_kind_of_query_matcher_applies() {
+        let mock = mock(json!({
+            "a": { "equalTo": "1" },
+            "b": { "contains": "x" },
+            "c": { "matches": "^[0-9]+$" }
+        }));
+        assert!(mock.matches(&Request::get("http://localhost/?a=1&b=axe&c=42")));
+        assert!(!mock.matches(&Request::get("http://localhost/?a=1&b=no&c=42")));
+        assert!(!mock.matches(&Request::get("http://localhost/?a=1&b=axe&c=x")));
+        assert!(!mock.matches(&Request::get("http://localhost/?a=2&b=axe&c=42")));
+    }
+
+    #[test]
+    fn has_exactly_matches_every_value_of_a_repeated_key() {
+        let mock = mock(json!({
+            "tag": { "hasExactly": [{ "equalTo": "a" }, { "equalTo": "b" }] }
+        }));
+        assert!(mock.matches(&Request::get("http://localhost/?tag=a&tag=b")));
+        assert!(mock.matches(&Request::get("http://localhost/?tag=b&tag=a")));
+        assert!(!mock.matches(&Request::get("http://localhost/?tag=a")));
+        assert!(!mock.matches(&Request::get("http://localhost/?tag=a&tag=b&tag=c")));
+    }
+
+    fn error(query_params: serde_json::Value) -> SpoofError {
+        let mapping = json!({ "request": { "queryParams": query_params } });
+        match Mock::try_from_mapping(serde_json::from_value(mapping).unwrap()) {
+            Err(e) => e,
+            Ok(_) => panic!("{} should be rejected", query_params),
         }
-        builder
+    }
+
+    #[test]
+    fn numbers_are_compared_as_written() {
+        let mock = mock(json!({ "a": { "equalTo": 1.5 }, "b": { "equalTo": 2 } }));
+        assert!(mock.matches(&Request::get("http://localhost/?a=1.5&b=2")));
+        assert!(!mock.matches(&Request::get("http://localhost/?a=1&b=2")));
+    }
+
+    #[test]
+    fn unusable_matchers_are_rejected_instead_of_ignored() {
+        let field = |query_params| error(query_params).field().to_string();
+        assert_eq!(
+            field(json!({ "a": { "equalTo": null } })),
+            "/request/queryParams/a/equalTo"
+        );
+        assert_eq!(
+            field(json!({ "a": { "equalTo": "1", "absent": "yes" } })),
+            "/request/queryParams/a"
+        );
+        assert_eq!(
+            field(json!({ "a": { "caseInsensitive": true } })),
+            "/request/queryParams/a"
+        );
+        assert_eq!(
+            field(json!({ "a": { "hasExactly": [{ "contains": "1" }] } })),
+            "/request/queryParams/a/hasExactly/0"
+        );
     }
 }
@@ -0,0 +1,29 @@
+use crate::error::SpoofError;
+use crate::matcher::query::QueryParamValuesMatcher;
+use crate::matcher::query_param_values;
+use crate::model::request::matcher::GenericMatcherStub;
+use crate::model::request::query::HttpQueryParamsStub;
+
+impl TryFrom<&HttpQueryParamsStub> for Vec<QueryParamValuesMatcher> {
+    type Error = SpoofError;
+
+    fn try_from(http_query_params: &HttpQueryParamsStub) -> Result<Self, Self::Error> {
+        http_query_params
+            .get_query_params_as_iter()
+            .ok_or_else(|| SpoofError::missing("/queryParams"))?
+            .filter(|it| it.is_has_exactly())
+            .map(|it| QueryParamValuesMatcher::try_from(&it))
+            .collect()
+    }
+}
+
+impl TryFrom<&GenericMatcherStub> for QueryParamValuesMatcher {
+    type Error = SpoofError;
+
+    fn try_from(query: &GenericMatcherStub) -> Result<Self, Self::Error> {
+        query
+            .get_has_exactly_as_strings()
+            .map(|values| query_param_values(query.key.as_str(), values))
+            .ok_or_else(|| query.missing("/queryParams", "hasExactly"))
+    }
+}
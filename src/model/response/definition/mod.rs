@@ -0,0 +1,287 @@
+use crate::error::SpoofError;
+use crate::model::mapping::validation::child_pointer;
+use crate::model::response::transformers::{ResponseTransformersStub, RESPONSE_TEMPLATE};
+use crate::model::response::ResponseRegistrable;
+use crate::response::fault::Fault;
+use crate::response::proxy::ProxyConfig;
+use crate::response::response_template::MockResponseBuilder;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use http::header::CONTENT_TYPE;
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseDefinition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base64_body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_delay_milliseconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fault: Option<Fault>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_proxy_request_headers: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remove_proxy_request_headers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_url_prefix_to_remove: Option<String>,
+    #[serde(flatten)]
+    pub transformers: ResponseTransformersStub,
+}
+
+impl ResponseDefinition {
+    fn proxy(&self) -> Result<Option<ProxyConfig>, SpoofError> {
+        let Some(base_url) = self.proxy_base_url.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut proxy = ProxyConfig::try_new(base_url).map_err(|e| e.at("/proxyBaseUrl"))?;
+        for (key, value) in header_values(self.additional_proxy_request_headers.as_ref()) {
+            proxy = proxy
+                .try_with_header(key.as_str(), value.as_str())
+                .map_err(|e| e.at(child_pointer("/additionalProxyRequestHeaders", key)))?
+        }
+        for (index, key) in self
+            .remove_proxy_request_headers
+            .iter()
+            .flatten()
+            .enumerate()
+        {
+            proxy = proxy.try_without_header(key.as_str()).map_err(|e| {
+                e.at(child_pointer(
+                    "/removeProxyRequestHeaders",
+                    &index.to_string(),
+                ))
+            })?
+        }
+        if let Some(prefix) = self.proxy_url_prefix_to_remove.as_ref() {
+            proxy = proxy.with_prefix_rewrite(prefix, "")
+        }
+        Ok(Some(proxy))
+    }
+}
+
+fn header_values(headers: Option<&Map<String, Value>>) -> impl Iterator<Item = (&String, String)> {
+    headers.into_iter().flatten().flat_map(|(key, value)| {
+        let values = match value {
+            Value::Array(values) => values.iter().map(header_value).collect(),
+            value => vec![header_value(value)],
+        };
+        values.into_iter().map(move |value| (key, value))
+    })
+}
+
+pub(crate) fn headers_to_json(headers: &HeaderMap) -> Map<String, Value> {
+    headers
+        .keys()
+        .map(|key| {
+            let mut values = headers
+                .get_all(key)
+                .iter()
+                .map(|value| Value::from(String::from_utf8_lossy(value.as_bytes()).to_string()))
+                .collect::<Vec<_>>();
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                Value::from(values)
+            };
+            (key.to_string(), value)
+        })
+        .collect()
+}
+
+fn header_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+impl ResponseRegistrable for ResponseDefinition {
+    fn try_register(
+        &self,
+        mut builder: MockResponseBuilder,
+    ) -> Result<MockResponseBuilder, SpoofError> {
+        if let Some(status) = self.status {
+            builder = builder
+                .try_with_status_code(status)
+                .map_err(|e| e.at("/status"))?
+        }
+        for (key, value) in header_values(self.headers.as_ref()) {
+            builder = builder
+                .try_with_header_append(key.as_str(), value.as_str())
+                .map_err(|e| e.at(child_pointer("/headers", key)))?
+        }
+        if let Some(json_body) = self.json_body.as_ref() {
+            builder = builder
+                .try_with_body_json(json_body)
+                .map_err(|e| e.at("/jsonBody"))?
+        } else if let Some(body) = self.body.as_ref() {
+            builder = builder.with_body_string(body.as_str())
+        } else if let Some(body) = self.base64_body.as_ref() {
+            let body = BASE64_STANDARD
+                .decode(body)
+                .map_err(|e| SpoofError::InvalidBody {
+                    field: "/base64Body".to_string(),
+                    reason: e.to_string(),
+                })?;
+            builder = builder.with_body_bytes(body)
+        } else if let Some(body_file_name) = self.body_file_name.as_ref() {
+            builder = builder.with_body_file(body_file_name)
+        }
+        if let Some(delay) = self.fixed_delay_milliseconds {
+            builder = builder.with_delay(Duration::from_millis(delay))
+        }
+        if let Some(fault) = self.fault {
+            builder = builder.with_fault(fault)
+        }
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.with_proxy(proxy)
+        }
+        self.transformers.try_register(builder)
+    }
+}
+
+impl TryFrom<&ResponseDefinition> for MockResponseBuilder {
+    type Error = SpoofError;
+
+    fn try_from(definition: &ResponseDefinition) -> Result<Self, Self::Error> {
+        definition.try_register(MockResponseBuilder::new())
+    }
+}
+
+impl From<&MockResponseBuilder> for ResponseDefinition {
+    fn from(builder: &MockResponseBuilder) -> Self {
+        let headers = builder.headers();
+        let mut definition = ResponseDefinition {
+            status: builder.status_code().map(|it| it.as_u16()),
+            headers: Some(headers_to_json(&headers)).filter(|it| !it.is_empty()),
+            body_file_name: builder
+                .body_file()
+                .map(|it| it.to_string_lossy().to_string()),
+            fixed_delay_milliseconds: builder.delay().map(|it| it.as_millis() as u64),
+            fault: builder.fault(),
+            ..Default::default()
+        };
+
+        if let Some(body) = builder.body() {
+            let is_json = headers
+                .get(CONTENT_TYPE)
+                .and_then(|it| it.to_str().ok())
+                .is_some_and(|it| it.contains("json"));
+            match serde_json::from_slice::<Value>(body) {
+                Ok(json) if is_json => definition.json_body = Some(json),
+                _ => match std::str::from_utf8(body) {
+                    Ok(text) => definition.body = Some(text.to_string()),
+                    Err(_) => definition.base64_body = Some(BASE64_STANDARD.encode(body)),
+                },
+            }
+        }
+
+        if let Some(proxy) = builder.proxy() {
+            definition.proxy_base_url = Some(proxy.base_url().to_string());
+            definition.additional_proxy_request_headers =
+                Some(headers_to_json(proxy.additional_headers())).filter(|it| !it.is_empty());
+            definition.remove_proxy_request_headers = Some(
+                proxy
+                    .removed_headers()
+                    .iter()
+                    .map(|it| it.to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .filter(|it| !it.is_empty());
+            definition.proxy_url_prefix_to_remove = proxy
+                .prefix_rewrite()
+                .filter(|(_, to)| to.is_empty())
+                .map(|(from, _)| from.to_string());
+        }
+
+        if builder.is_templated() {
+            definition.transformers.transformers = Some(vec![RESPONSE_TEMPLATE.to_string()]);
+        }
+        definition
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::mapping::validation::validate;
+    use serde_json::json;
+
+    fn definition(value: Value) -> ResponseDefinition {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn register_error(value: Value) -> SpoofError {
+        MockResponseBuilder::try_from(&definition(value)).unwrap_err()
+    }
+
+    #[test]
+    fn invalid_proxy_base_url_is_an_error() {
+        let error = register_error(json!({ "proxyBaseUrl": "not a url" }));
+        assert!(matches!(error, SpoofError::InvalidUrl { .. }));
+        assert_eq!(error.field(), "/proxyBaseUrl");
+    }
+
+    #[test]
+    fn invalid_additional_proxy_header_value_is_an_error() {
+        let error = register_error(json!({
+            "proxyBaseUrl": "http://localhost:8080",
+            "additionalProxyRequestHeaders": { "X-A": "bad\u{1}value" }
+        }));
+        assert!(matches!(error, SpoofError::InvalidHeaderValue { .. }));
+        assert_eq!(error.field(), "/additionalProxyRequestHeaders/X-A");
+    }
+
+    #[test]
+    fn invalid_removed_proxy_header_name_is_an_error() {
+        let error = register_error(json!({
+            "proxyBaseUrl": "http://localhost:8080",
+            "removeProxyRequestHeaders": ["x-ok", "bad name"]
+        }));
+        assert!(matches!(error, SpoofError::InvalidHeaderName { .. }));
+        assert_eq!(error.field(), "/removeProxyRequestHeaders/1");
+    }
+
+    #[test]
+    fn validator_reports_invalid_proxy_headers() {
+        let errors = validate(
+            &json!({
+                "request": {},
+                "response": {
+                    "proxyBaseUrl": "http://localhost:8080",
+                    "additionalProxyRequestHeaders": { "X-A": "bad\u{1}value" },
+                    "removeProxyRequestHeaders": ["bad name"]
+                }
+            }),
+            "",
+        );
+        let pointers = errors
+            .iter()
+            .map(|it| it.pointer.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pointers,
+            [
+                "/response/additionalProxyRequestHeaders/X-A",
+                "/response/removeProxyRequestHeaders/0"
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use crate::error::SpoofError;
+use crate::response::response_template::MockResponseBuilder;
+
+pub mod definition;
+pub mod sequence;
+pub mod transformers;
+
+pub trait ResponseRegistrable {
+    fn try_register(&self, builder: MockResponseBuilder)
+        -> Result<MockResponseBuilder, SpoofError>;
+}
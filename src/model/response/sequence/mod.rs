@@ -0,0 +1,96 @@
+use crate::core::mock::StubMappingBuilder;
+use crate::error::SpoofError;
+use crate::model::mapping::validation::child_pointer;
+use crate::model::request::MockRegistrable;
+use crate::model::response::definition::ResponseDefinition;
+use crate::response::response_template::MockResponseBuilder;
+use crate::response::sequence::SequencePolicy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseSequenceStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<Vec<WeightedResponseStub>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_policy: Option<SequencePolicy>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedResponseStub {
+    #[serde(flatten)]
+    pub response: ResponseDefinition,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+}
+
+impl MockRegistrable for ResponseSequenceStub {
+    fn try_register(
+        &self,
+        mut builder: StubMappingBuilder,
+    ) -> Result<StubMappingBuilder, SpoofError> {
+        if let Some(responses) = self.responses.as_ref().filter(|it| !it.is_empty()) {
+            let policy = match self.sequence_policy {
+                Some(policy) => policy,
+                // Weights only mean something when picking at random
+                None if responses.iter().any(|it| it.weight.is_some()) => SequencePolicy::Random,
+                None => SequencePolicy::default(),
+            };
+            let responses = responses
+                .iter()
+                .enumerate()
+                .map(|(index, it)| {
+                    MockResponseBuilder::try_from(&it.response)
+                        .map(|response| (response, it.weight.unwrap_or(1)))
+                        .map_err(|e| e.within(&child_pointer("/responses", &index.to_string())))
+                })
+                .collect::<Result<_, _>>()?;
+            builder = builder
+                .will_return_weighted(responses)
+                .with_sequence_policy(policy)
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mock::Mock;
+    use serde_json::json;
+
+    fn policy(value: serde_json::Value) -> SequencePolicy {
+        let stub: ResponseSequenceStub = serde_json::from_value(value).unwrap();
+        stub.try_register(Mock::stub_for(|_: &_| true))
+            .unwrap()
+            .sequence_policy
+    }
+
+    #[test]
+    fn weights_default_to_random_policy() {
+        let responses = json!([{ "status": 200, "weight": 3 }, { "status": 500 }]);
+        assert_eq!(
+            policy(json!({ "responses": responses })),
+            SequencePolicy::Random
+        );
+    }
+
+    #[test]
+    fn unweighted_responses_default_to_stick_on_last() {
+        let responses = json!([{ "status": 200 }, { "status": 500 }]);
+        assert_eq!(
+            policy(json!({ "responses": responses })),
+            SequencePolicy::StickOnLast
+        );
+    }
+
+    #[test]
+    fn explicit_policy_wins_over_weights() {
+        let responses = json!([{ "status": 200, "weight": 3 }, { "status": 500 }]);
+        assert_eq!(
+            policy(json!({ "responses": responses, "sequencePolicy": "loop" })),
+            SequencePolicy::Loop
+        );
+    }
+}
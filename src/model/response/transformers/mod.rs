@@ -0,0 +1,34 @@
+use crate::error::SpoofError;
+use crate::model::response::ResponseRegistrable;
+use crate::response::response_template::MockResponseBuilder;
+use serde::{Deserialize, Serialize};
+
+pub const RESPONSE_TEMPLATE: &str = "response-template";
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTransformersStub {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transformers: Option<Vec<String>>,
+}
+
+impl ResponseTransformersStub {
+    pub fn is_templated(&self) -> bool {
+        self.transformers
+            .iter()
+            .flatten()
+            .any(|transformer| transformer == RESPONSE_TEMPLATE)
+    }
+}
+
+impl ResponseRegistrable for ResponseTransformersStub {
+    fn try_register(
+        &self,
+        mut builder: MockResponseBuilder,
+    ) -> Result<MockResponseBuilder, SpoofError> {
+        if self.is_templated() {
+            builder = builder.with_templating()
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,62 @@
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use x509_parser::extensions::GeneralName;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ClientCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub subject_alt_names: Vec<String>,
+    pub sha256_fingerprint: String,
+    pub der: Vec<u8>,
+}
+
+impl ClientCertificate {
+    pub(crate) fn from_der(der: &[u8]) -> Result<Self, String> {
+        let (_, certificate) = x509_parser::parse_x509_certificate(der)
+            .map_err(|e| format!("Cannot parse client certificate : {}", e))?;
+
+        let subject_alt_names = certificate
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|extension| {
+                extension
+                    .value
+                    .general_names
+                    .iter()
+                    .filter_map(general_name)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            subject: certificate.subject().to_string(),
+            issuer: certificate.issuer().to_string(),
+            subject_alt_names,
+            sha256_fingerprint: fingerprint(der),
+            der: der.to_vec(),
+        })
+    }
+}
+
+fn general_name(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DNSName(name) | GeneralName::RFC822Name(name) | GeneralName::URI(name) => {
+            Some(name.to_string())
+        }
+        GeneralName::IPAddress(bytes) => match bytes.len() {
+            4 => Some(IpAddr::from(<[u8; 4]>::try_from(*bytes).ok()?).to_string()),
+            16 => Some(IpAddr::from(<[u8; 16]>::try_from(*bytes).ok()?).to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
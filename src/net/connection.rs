@@ -0,0 +1,126 @@
+use crate::net::certificate::ClientCertificate;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ConnectionInfo {
+    pub(crate) secure: bool,
+    pub(crate) client_certificate: Option<Arc<ClientCertificate>>,
+    pub(crate) remote_address: Option<SocketAddr>,
+    pub(crate) listener: Option<Arc<str>>,
+    pub(crate) trust_forwarded_headers: bool,
+    pub(crate) reset: Arc<AtomicBool>,
+}
+
+impl ConnectionInfo {
+    pub(crate) fn request_reset(&self) {
+        self.reset.store(true, Ordering::Relaxed);
+    }
+}
+
+pub(crate) struct ResettableStream {
+    stream: TcpStream,
+    reset: Arc<AtomicBool>,
+}
+
+impl ResettableStream {
+    pub(crate) fn new(stream: TcpStream, connection: &ConnectionInfo) -> Self {
+        Self {
+            stream,
+            reset: Arc::clone(&connection.reset),
+        }
+    }
+}
+
+impl Drop for ResettableStream {
+    fn drop(&mut self) {
+        // Closing with a zero linger discards unsent data and answers with RST
+        if self.reset.load(Ordering::Relaxed) {
+            if let Err(e) = self.stream.set_linger(Some(Duration::ZERO)) {
+                log::debug!("Cannot reset connection : {}", e);
+            }
+        }
+    }
+}
+
+impl AsyncRead for ResettableStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ResettableStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    async fn read_after_drop(reset: bool) -> io::Result<usize> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+
+        let connection = ConnectionInfo::default();
+        let stream = ResettableStream::new(stream, &connection);
+        if reset {
+            connection.request_reset();
+        }
+        drop(stream);
+
+        client.read(&mut [0; 16]).await
+    }
+
+    #[tokio::test]
+    async fn dropped_stream_closes_cleanly() {
+        assert_eq!(read_after_drop(false).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn dropped_stream_resets_when_requested() {
+        let error = read_after_drop(true).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionReset);
+    }
+}
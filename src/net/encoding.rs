@@ -0,0 +1,276 @@
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use http::header::CONTENT_ENCODING;
+use http::HeaderMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DecodeError {
+    TooLarge {
+        max_size: usize,
+    },
+    Invalid(String),
+}
+
+impl DecodeError {
+    fn invalid(error: io::Error) -> Self {
+        DecodeError::Invalid(error.to_string())
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::TooLarge { max_size } => {
+                write!(f, "decoded body is larger than {} bytes", max_size)
+            }
+            DecodeError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+fn read_limited<R: Read>(reader: R, max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::new();
+    reader
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decoded)
+        .map_err(DecodeError::invalid)?;
+    if decoded.len() > max_size {
+        return Err(DecodeError::TooLarge { max_size });
+    }
+    Ok(decoded)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ContentEncoding {
+    Identity,
+    Gzip,
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+impl ContentEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Identity => "identity",
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+
+    pub fn decode(&self, body: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+        match self {
+            ContentEncoding::Identity => read_limited(body, max_size),
+            ContentEncoding::Gzip => read_limited(GzDecoder::new(body), max_size),
+            ContentEncoding::Deflate => {
+                // `deflate` is meant to be zlib wrapped, but plenty of clients send raw deflate streams
+                match read_limited(ZlibDecoder::new(body), max_size) {
+                    Err(DecodeError::Invalid(_)) => {
+                        read_limited(DeflateDecoder::new(body), max_size)
+                    }
+                    result => result,
+                }
+            }
+            ContentEncoding::Brotli => {
+                read_limited(brotli::Decompressor::new(body, 4096), max_size)
+            }
+            ContentEncoding::Zstd => read_limited(
+                zstd::stream::read::Decoder::new(body).map_err(DecodeError::invalid)?,
+                max_size,
+            ),
+        }
+    }
+
+    pub fn encode(&self, body: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Identity => Ok(body.to_vec()),
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            ContentEncoding::Brotli => {
+                let mut encoded = Vec::new();
+                {
+                    let mut encoder = brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22);
+                    encoder.write_all(body)?;
+                }
+                Ok(encoded)
+            }
+            ContentEncoding::Zstd => zstd::stream::encode_all(body, 0),
+        }
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "identity" => Ok(ContentEncoding::Identity),
+            "gzip" | "x-gzip" => Ok(ContentEncoding::Gzip),
+            "deflate" => Ok(ContentEncoding::Deflate),
+            "br" => Ok(ContentEncoding::Brotli),
+            "zstd" => Ok(ContentEncoding::Zstd),
+            other => Err(format!("Unsupported content encoding : {}", other)),
+        }
+    }
+}
+
+pub(crate) fn content_encodings(headers: &HeaderMap) -> Result<Vec<ContentEncoding>, String> {
+    headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter(|value| !value.trim().is_empty())
+        .map(ContentEncoding::from_str)
+        .collect()
+}
+
+pub(crate) fn decode_body(
+    headers: &HeaderMap,
+    body: &[u8],
+    max_size: usize,
+) -> Result<Option<Vec<u8>>, DecodeError> {
+    let encodings = content_encodings(headers).map_err(DecodeError::Invalid)?;
+    if encodings
+        .iter()
+        .all(|encoding| *encoding == ContentEncoding::Identity)
+    {
+        return Ok(None);
+    }
+
+    encodings
+        .iter()
+        .rev()
+        .try_fold(body.to_vec(), |body, encoding| {
+            encoding.decode(&body, max_size).map_err(|e| match e {
+                DecodeError::Invalid(e) => DecodeError::Invalid(format!(
+                    "Cannot decode {} request body : {}",
+                    encoding.as_str(),
+                    e
+                )),
+                e => e,
+            })
+        })
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    const ENCODINGS: [ContentEncoding; 5] = [
+        ContentEncoding::Identity,
+        ContentEncoding::Gzip,
+        ContentEncoding::Deflate,
+        ContentEncoding::Brotli,
+        ContentEncoding::Zstd,
+    ];
+
+    fn headers(content_encoding: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_ENCODING,
+            HeaderValue::from_str(content_encoding).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn decode_undoes_encode() {
+        let body = b"{\"name\":\"spoof\"}".repeat(10);
+        for encoding in ENCODINGS {
+            let encoded = encoding.encode(&body).unwrap();
+            assert_eq!(
+                encoding.decode(&encoded, 1024).unwrap(),
+                body,
+                "{:?}",
+                encoding
+            );
+        }
+    }
+
+    #[test]
+    fn decode_stops_past_max_size() {
+        let body = vec![0u8; 1024 * 1024];
+        for encoding in ENCODINGS {
+            let encoded = encoding.encode(&body).unwrap();
+            assert_eq!(
+                encoding.decode(&encoded, 1000),
+                Err(DecodeError::TooLarge { max_size: 1000 }),
+                "{:?}",
+                encoding
+            );
+            assert_eq!(
+                encoding.decode(&encoded, body.len()).unwrap().len(),
+                body.len()
+            );
+        }
+    }
+
+    #[test]
+    fn deflate_accepts_raw_streams() {
+        let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"raw deflate").unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        assert_eq!(
+            ContentEncoding::Deflate.decode(&encoded, 1024).unwrap(),
+            b"raw deflate"
+        );
+    }
+
+    #[test]
+    fn decode_body_undoes_codings_in_reverse_order() {
+        let gzipped = ContentEncoding::Gzip.encode(b"hello").unwrap();
+        let encoded = ContentEncoding::Brotli.encode(&gzipped).unwrap();
+
+        assert_eq!(
+            decode_body(&headers("gzip, br"), &encoded, 1024).unwrap(),
+            Some(b"hello".to_vec())
+        );
+        assert_eq!(
+            decode_body(&headers("identity"), b"hello", 1024).unwrap(),
+            None
+        );
+        assert_eq!(
+            decode_body(&HeaderMap::new(), b"hello", 1024).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_body_reports_invalid_and_oversized_bodies() {
+        assert!(matches!(
+            decode_body(&headers("gzip"), b"not gzip", 1024),
+            Err(DecodeError::Invalid(_))
+        ));
+        assert!(matches!(
+            decode_body(&headers("compress"), b"hello", 1024),
+            Err(DecodeError::Invalid(_))
+        ));
+
+        let bomb = ContentEncoding::Gzip
+            .encode(&vec![0u8; 1024 * 1024])
+            .unwrap();
+        assert_eq!(
+            decode_body(&headers("gzip"), &bomb, 4096),
+            Err(DecodeError::TooLarge { max_size: 4096 })
+        );
+    }
+}
@@ -1 +1,4 @@
+pub mod certificate;
+pub(crate) mod connection;
+pub mod encoding;
 pub mod request;
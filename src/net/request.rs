@@ -1,36 +1,312 @@
-use http::{HeaderMap, Method};
+use crate::net::certificate::ClientCertificate;
+use crate::net::connection::ConnectionInfo;
+use crate::net::encoding::{decode_body, DecodeError};
+use http::header::{HeaderName, CONTENT_ENCODING, CONTENT_LENGTH, FORWARDED};
+use http::{HeaderMap, Method, Version};
 use http_body_util::BodyExt;
+use hyper::body::Incoming;
+use sha2::{Digest, Sha256};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 use url::Url;
 
+pub const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Clone, Debug)]
+pub struct RequestBodySettings {
+    pub max_size: usize,
+    pub digest_oversized: bool,
+}
+
+impl Default for RequestBodySettings {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_MAX_BODY_SIZE,
+            digest_oversized: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum BodyStatus {
+    #[default]
+    Complete,
+    Digested { size: u64, sha256: String },
+    TooLarge { max_size: usize },
+    Failed(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct Request {
     pub url: Url,
     pub method: Method,
+    pub version: Version,
     pub headers: HeaderMap,
     pub body: Vec<u8>,
+    pub raw_body: Vec<u8>,
+    pub client_certificate: Option<Arc<ClientCertificate>>,
+    pub body_status: BodyStatus,
+    pub remote_address: Option<SocketAddr>,
+    pub client_ip: Option<IpAddr>,
+    pub listener: Option<String>,
 }
 
 impl Request {
-    pub(crate) async fn from_hyper(request: hyper::Request<hyper::body::Incoming>) -> Self {
+    pub(crate) async fn from_hyper(
+        request: hyper::Request<Incoming>,
+        settings: &RequestBodySettings,
+    ) -> Self {
         let (parts, body) = request.into_parts();
+        let connection = parts
+            .extensions
+            .get::<ConnectionInfo>()
+            .cloned()
+            .unwrap_or_default();
+        let scheme = if connection.secure { "https" } else { "http" };
         let url = match parts.uri.authority() {
             Some(_) => parts.uri.to_string(),
-            None => format!("http://localhost{}", parts.uri),
+            None => format!("{}://localhost{}", scheme, parts.uri),
         }
         .parse()
         .unwrap();
 
-        let body = body
-            .collect()
-            .await
-            .expect("Failed to read request body.")
-            .to_bytes();
+        let forwarded_for = connection
+            .trust_forwarded_headers
+            .then(|| forwarded_for(&parts.headers))
+            .flatten();
+        let client_ip = forwarded_for.or(connection.remote_address.map(|it| it.ip()));
+
+        let (raw_body, body_status) = read_body(body, &parts.headers, settings).await;
+
+        // The size limit also holds once decoded, a small compressed body can expand a lot
+        let (body, body_status) = match body_status {
+            BodyStatus::Complete => {
+                match decode(&parts.headers, &raw_body, settings.max_size).await {
+                    Ok(Some(decoded)) => (decoded, BodyStatus::Complete),
+                    Ok(None) => (raw_body.clone(), BodyStatus::Complete),
+                    Err(DecodeError::TooLarge { max_size }) if !settings.digest_oversized => {
+                        (Vec::new(), BodyStatus::TooLarge { max_size })
+                    }
+                    Err(e) => {
+                        log::debug!("{}, matching on the raw body", e);
+                        (raw_body.clone(), BodyStatus::Complete)
+                    }
+                }
+            }
+            status => (raw_body.clone(), status),
+        };
 
         Self {
             url,
             method: parts.method,
+            version: parts.version,
             headers: parts.headers,
-            body: body.to_vec(),
+            body,
+            raw_body,
+            client_certificate: connection.client_certificate,
+            body_status,
+            remote_address: connection.remote_address,
+            client_ip,
+            listener: connection.listener.as_deref().map(str::to_string),
+        }
+    }
+}
+
+fn forwarded_for(headers: &HeaderMap) -> Option<IpAddr> {
+    let x_forwarded_for = HeaderName::from_static("x-forwarded-for");
+    let forwarded = headers
+        .get(FORWARDED)
+        .and_then(|it| it.to_str().ok())
+        .and_then(|it| it.split(',').next())
+        .and_then(|element| {
+            element.split(';').find_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                name.eq_ignore_ascii_case("for").then_some(value)
+            })
+        });
+    let node = match forwarded {
+        Some(node) => node,
+        None => headers
+            .get(x_forwarded_for)
+            .and_then(|it| it.to_str().ok())
+            .and_then(|it| it.split(',').next())?,
+    };
+    parse_node(node.trim().trim_matches('"'))
+}
+
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Ok(address) = node.parse::<IpAddr>() {
+        return Some(address);
+    }
+    if let Some(address) = node
+        .strip_prefix('[')
+        .and_then(|it| it.split_once(']'))
+        .map(|(address, _)| address)
+    {
+        return address.parse().ok();
+    }
+    node.parse::<SocketAddr>().ok().map(|it| it.ip())
+}
+
+async fn decode(
+    headers: &HeaderMap,
+    raw_body: &[u8],
+    max_size: usize,
+) -> Result<Option<Vec<u8>>, DecodeError> {
+    if !headers.contains_key(CONTENT_ENCODING) {
+        return Ok(None);
+    }
+    let headers = headers.clone();
+    let raw_body = raw_body.to_vec();
+    tokio::task::spawn_blocking(move || decode_body(&headers, &raw_body, max_size))
+        .await
+        .unwrap_or_else(|e| Err(DecodeError::Invalid(e.to_string())))
+}
+
+async fn read_body(
+    mut body: Incoming,
+    headers: &HeaderMap,
+    settings: &RequestBodySettings,
+) -> (Vec<u8>, BodyStatus) {
+    let content_length = headers
+        .get(CONTENT_LENGTH)
+        .and_then(|it| it.to_str().ok())
+        .and_then(|it| it.parse::<u64>().ok());
+    let too_large = BodyStatus::TooLarge {
+        max_size: settings.max_size,
+    };
+    if !settings.digest_oversized && content_length.is_some_and(|it| it > settings.max_size as u64)
+    {
+        return (Vec::new(), too_large);
+    }
+
+    let mut buffer = Vec::new();
+    let mut digest: Option<(Sha256, u64)> = None;
+    while let Some(frame) = body.frame().await {
+        let data = match frame {
+            Ok(frame) => match frame.into_data() {
+                Ok(data) => data,
+                // Trailers
+                Err(_) => continue,
+            },
+            Err(e) => return (buffer, BodyStatus::Failed(e.to_string())),
+        };
+
+        if let Some((hasher, size)) = digest.as_mut() {
+            hasher.update(&data);
+            *size += data.len() as u64;
+        } else if buffer.len() + data.len() <= settings.max_size {
+            buffer.extend_from_slice(&data);
+        } else if settings.digest_oversized {
+            let mut hasher = Sha256::new();
+            hasher.update(&buffer);
+            hasher.update(&data);
+            digest = Some((hasher, (buffer.len() + data.len()) as u64));
+            buffer = Vec::new();
+        } else {
+            return (Vec::new(), too_large);
+        }
+    }
+
+    match digest {
+        Some((hasher, size)) => {
+            let sha256 = hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            (buffer, BodyStatus::Digested { size, sha256 })
+        }
+        None => (buffer, BodyStatus::Complete),
+    }
+}
+
+#[cfg(test)]
+impl Request {
+    pub(crate) fn get(url: &str) -> Self {
+        Self {
+            url: url.parse().expect("test URL must be valid"),
+            method: Method::GET,
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+            raw_body: Vec::new(),
+            client_certificate: None,
+            body_status: BodyStatus::Complete,
+            remote_address: None,
+            client_ip: None,
+            listener: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn forwarded(headers: &[(&'static str, &'static str)]) -> Option<IpAddr> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        forwarded_for(&map)
+    }
+
+    fn ip(address: &str) -> Option<IpAddr> {
+        Some(address.parse().unwrap())
+    }
+
+    #[test]
+    fn reads_the_first_forwarded_element() {
+        assert_eq!(
+            forwarded(&[("forwarded", "for=192.0.2.60;proto=http, for=198.51.100.17")]),
+            ip("192.0.2.60")
+        );
+        assert_eq!(
+            forwarded(&[("forwarded", "proto=https; For=\"[2001:db8:cafe::17]:4711\"")]),
+            ip("2001:db8:cafe::17")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_x_forwarded_for() {
+        assert_eq!(
+            forwarded(&[("x-forwarded-for", "203.0.113.195, 70.41.3.18")]),
+            ip("203.0.113.195")
+        );
+        assert_eq!(
+            forwarded(&[
+                ("forwarded", "proto=https"),
+                ("x-forwarded-for", "203.0.113.195")
+            ]),
+            ip("203.0.113.195")
+        );
+    }
+
+    #[test]
+    fn prefers_forwarded_over_x_forwarded_for() {
+        assert_eq!(
+            forwarded(&[
+                ("x-forwarded-for", "203.0.113.195"),
+                ("forwarded", "for=192.0.2.60"),
+            ]),
+            ip("192.0.2.60")
+        );
+    }
+
+    #[test]
+    fn ignores_obfuscated_or_missing_nodes() {
+        assert_eq!(forwarded(&[("forwarded", "for=_hidden")]), None);
+        assert_eq!(forwarded(&[("forwarded", "for=unknown")]), None);
+        assert_eq!(forwarded(&[]), None);
+    }
+
+    #[test]
+    fn parses_nodes_with_ports() {
+        assert_eq!(parse_node("192.0.2.1:4711"), ip("192.0.2.1"));
+        assert_eq!(parse_node("[2001:db8::1]:4711"), ip("2001:db8::1"));
+        assert_eq!(parse_node("2001:db8::1"), ip("2001:db8::1"));
+        assert_eq!(parse_node("[2001:db8::1]"), ip("2001:db8::1"));
+    }
+}
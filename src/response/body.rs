@@ -0,0 +1,31 @@
+use bytes::{Bytes, BytesMut};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::Frame;
+use std::io;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub type ResponseBody = UnsyncBoxBody<Bytes, io::Error>;
+
+pub(crate) fn full_body<B>(body: B) -> ResponseBody
+where
+    B: Into<Bytes>,
+{
+    boxed(Full::new(body.into()))
+}
+
+pub(crate) fn boxed(body: Full<Bytes>) -> ResponseBody {
+    body.map_err(|never| match never {}).boxed_unsync()
+}
+
+pub(crate) fn file_body(file: File) -> ResponseBody {
+    let frames = futures_util::stream::try_unfold(file, |mut file| async move {
+        let mut chunk = BytesMut::with_capacity(CHUNK_SIZE);
+        let read = file.read_buf(&mut chunk).await?;
+        Ok((read > 0).then(|| (Frame::data(chunk.freeze()), file)))
+    });
+    StreamBody::new(frames).boxed_unsync()
+}
@@ -0,0 +1,117 @@
+use crate::net::encoding::ContentEncoding;
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, VARY};
+use http::{HeaderMap, HeaderValue};
+use std::str::FromStr;
+
+const NEGOTIABLE_ENCODINGS: [ContentEncoding; 3] = [
+    ContentEncoding::Brotli,
+    ContentEncoding::Gzip,
+    ContentEncoding::Deflate,
+];
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum ResponseCompression {
+    #[default]
+    Disabled,
+    Negotiate,
+    Force(ContentEncoding),
+    Mislabel {
+        encoding: ContentEncoding,
+        declared: String,
+    },
+}
+
+impl ResponseCompression {
+    pub(crate) fn apply(
+        &self,
+        request_headers: &HeaderMap,
+        headers: &mut HeaderMap,
+        body: Vec<u8>,
+    ) -> Vec<u8> {
+        // Stubs that set their own `Content-Encoding` are assumed to carry an already encoded body
+        if body.is_empty() || headers.contains_key(CONTENT_ENCODING) {
+            return body;
+        }
+
+        let (encoding, declared) = match self {
+            ResponseCompression::Disabled => return body,
+            ResponseCompression::Negotiate => {
+                headers.append(VARY, HeaderValue::from_static("Accept-Encoding"));
+                match negotiate(request_headers) {
+                    Some(encoding) => (encoding, encoding.as_str().to_string()),
+                    None => return body,
+                }
+            }
+            ResponseCompression::Force(encoding) => (*encoding, encoding.as_str().to_string()),
+            ResponseCompression::Mislabel { encoding, declared } => (*encoding, declared.clone()),
+        };
+
+        match (encoding.encode(&body), HeaderValue::from_str(&declared)) {
+            (Ok(encoded), Ok(declared)) => {
+                headers.insert(CONTENT_ENCODING, declared);
+                encoded
+            }
+            (Err(e), _) => {
+                log::error!(
+                    "Cannot encode response body as {} : {}",
+                    encoding.as_str(),
+                    e
+                );
+                body
+            }
+            (_, Err(e)) => {
+                log::error!("Cannot use {} as Content-Encoding : {}", declared, e);
+                body
+            }
+        }
+    }
+}
+
+fn negotiate(request_headers: &HeaderMap) -> Option<ContentEncoding> {
+    let accepted = request_headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(parse_weighted_coding)
+        .collect::<Vec<_>>();
+
+    let weight_of = |encoding: &ContentEncoding| {
+        accepted
+            .iter()
+            .find(|(coding, _)| coding == encoding.as_str())
+            .or_else(|| accepted.iter().find(|(coding, _)| coding == "*"))
+            .map(|(_, weight)| *weight)
+            .unwrap_or_default()
+    };
+
+    NEGOTIABLE_ENCODINGS
+        .iter()
+        .map(|encoding| (*encoding, weight_of(encoding)))
+        .filter(|(_, weight)| *weight > 0.0)
+        .fold(
+            None,
+            |best: Option<(ContentEncoding, f32)>, (encoding, weight)| match best {
+                Some((_, best_weight)) if best_weight >= weight => best,
+                _ => Some((encoding, weight)),
+            },
+        )
+        .map(|(encoding, _)| encoding)
+}
+
+fn parse_weighted_coding(value: &str) -> Option<(String, f32)> {
+    let mut parts = value.split(';').map(str::trim);
+    let coding = parts
+        .next()
+        .filter(|it| !it.is_empty())?
+        .to_ascii_lowercase();
+    let coding = match ContentEncoding::from_str(&coding) {
+        Ok(encoding) => encoding.as_str().to_string(),
+        Err(_) => coding,
+    };
+    let weight = parts
+        .find_map(|param| param.strip_prefix("q="))
+        .map(|weight| weight.parse::<f32>().unwrap_or_default())
+        .unwrap_or(1.0);
+    Some((coding, weight))
+}
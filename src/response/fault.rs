@@ -0,0 +1,46 @@
+use crate::response::body::ResponseBody;
+use bytes::Bytes;
+use http::{Response, StatusCode};
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Fault {
+    EmptyResponse,
+    ConnectionResetByPeer,
+    MalformedResponseChunk,
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Fault::EmptyResponse => "EMPTY_RESPONSE",
+            Fault::ConnectionResetByPeer => "CONNECTION_RESET_BY_PEER",
+            Fault::MalformedResponseChunk => "MALFORMED_RESPONSE_CHUNK",
+        };
+        f.write_str(name)
+    }
+}
+
+impl std::error::Error for Fault {}
+
+impl Fault {
+    pub(crate) fn into_response(self) -> Result<Response<ResponseBody>, Fault> {
+        match self {
+            Fault::EmptyResponse | Fault::ConnectionResetByPeer => Err(self),
+            Fault::MalformedResponseChunk => {
+                let frames = futures_util::stream::iter([
+                    Ok(Frame::data(Bytes::from_static(b"lorem ipsum"))),
+                    Err(io::Error::other(self.to_string())),
+                ]);
+                let mut response = Response::new(StreamBody::new(frames).boxed_unsync());
+                *response.status_mut() = StatusCode::OK;
+                Ok(response)
+            }
+        }
+    }
+}
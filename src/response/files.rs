@@ -0,0 +1,134 @@
+use crate::net::request::Request;
+use crate::response::body::{file_body, full_body, ResponseBody};
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use http::{HeaderMap, HeaderValue, Method, Response, StatusCode};
+use percent_encoding::percent_decode_str;
+use std::path::{Component, Path, PathBuf};
+
+pub(crate) const STREAMING_THRESHOLD: u64 = 1024 * 1024;
+
+pub(crate) fn resolve(root: &Path, name: &Path) -> Option<PathBuf> {
+    name.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        .then(|| root.join(name))
+}
+
+pub(crate) fn content_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let content_type = match extension.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "css" => "text/css",
+        "js" => "application/javascript",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "bin" => "application/octet-stream",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+pub(crate) async fn serve_static(root: &Path, request: &Request) -> Option<Response<ResponseBody>> {
+    if request.method != Method::GET && request.method != Method::HEAD {
+        return None;
+    }
+
+    // Paths arrive percent-encoded, e.g. `my%20file.txt`
+    let name = percent_decode_str(request.url.path().trim_start_matches('/'))
+        .decode_utf8()
+        .ok()?;
+    let mut path = resolve(root, Path::new(name.as_ref()))?;
+    if tokio::fs::metadata(&path).await.ok()?.is_dir() {
+        path = path.join("index.html");
+    }
+
+    let mut headers = HeaderMap::new();
+    if let Some(content_type) = content_type(&path) {
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    }
+    stream_file(&path, StatusCode::OK, headers).await.ok()
+}
+
+pub(crate) async fn stream_file(
+    path: &Path,
+    status: StatusCode,
+    mut headers: HeaderMap,
+) -> std::io::Result<Response<ResponseBody>> {
+    let file = tokio::fs::File::open(path).await?;
+    let metadata = file.metadata().await?;
+    if !metadata.is_file() {
+        return Err(std::io::Error::other(format!(
+            "{} is not a file",
+            path.display()
+        )));
+    }
+    headers.insert(CONTENT_LENGTH, HeaderValue::from(metadata.len()));
+
+    let mut response = Response::new(file_body(file));
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    Ok(response)
+}
+
+pub(crate) fn missing_file(path: &Path, e: std::io::Error) -> Response<ResponseBody> {
+    log::error!("Cannot read body file {} : {}", path.display(), e);
+    let mut response = Response::new(full_body(format!(
+        "Cannot read body file {}",
+        path.display()
+    )));
+    *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::request::BodyStatus;
+
+    fn get(path: &str) -> Request {
+        Request {
+            url: format!("http://localhost{}", path).parse().unwrap(),
+            method: Method::GET,
+            version: http::Version::HTTP_11,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+            raw_body: Vec::new(),
+            client_certificate: None,
+            body_status: BodyStatus::Complete,
+            remote_address: None,
+            client_ip: None,
+            listener: None,
+        }
+    }
+
+    #[test]
+    fn resolve_stays_in_root() {
+        let root = Path::new("/srv/files");
+        assert_eq!(
+            resolve(root, Path::new("a/./b.txt")),
+            Some(root.join("a/./b.txt"))
+        );
+        assert_eq!(resolve(root, Path::new("../secret")), None);
+        assert_eq!(resolve(root, Path::new("/etc/passwd")), None);
+    }
+
+    #[tokio::test]
+    async fn serves_percent_encoded_paths() {
+        let root = std::env::temp_dir().join(format!("spoof-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("my file.txt"), "content").unwrap();
+
+        let served = serve_static(&root, &get("/my%20file.txt")).await;
+        let escaped = serve_static(&root, &get("/..%2Fmy%20file.txt")).await;
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(served.unwrap().status(), StatusCode::OK);
+        assert!(escaped.is_none());
+    }
+}
@@ -1 +1,8 @@
-mod response_template;
+pub mod body;
+pub mod compression;
+pub mod fault;
+pub(crate) mod files;
+pub mod proxy;
+pub mod response_template;
+pub mod sequence;
+pub mod templating;
@@ -0,0 +1,335 @@
+use crate::error::SpoofError;
+use crate::matcher::header::{header_name, header_value};
+use crate::net::request::{BodyStatus, Request};
+use bytes::Bytes;
+use http::header::{
+    CONNECTION, HOST, PROXY_AUTHENTICATE, PROXY_AUTHORIZATION, TE, TRAILER, TRANSFER_ENCODING,
+    UPGRADE,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use std::fmt::{Debug, Display};
+use url::Url;
+
+const HOP_BY_HOP_HEADERS: [HeaderName; 8] = [
+    CONNECTION,
+    HeaderName::from_static("keep-alive"),
+    PROXY_AUTHENTICATE,
+    PROXY_AUTHORIZATION,
+    TE,
+    TRAILER,
+    TRANSFER_ENCODING,
+    UPGRADE,
+];
+
+pub(crate) type ProxyClient = Client<HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+pub(crate) fn proxy_client() -> ProxyClient {
+    let builder = HttpsConnectorBuilder::new()
+        .with_native_roots()
+        .unwrap_or_else(|e| {
+            log::warn!(
+                "Cannot load the system CA certificates, trusting the Mozilla ones : {}",
+                e
+            );
+            HttpsConnectorBuilder::new().with_webpki_roots()
+        });
+    let connector = builder.https_or_http().enable_http1().build();
+    Client::builder(TokioExecutor::new()).build(connector)
+}
+
+#[derive(Clone, Debug)]
+pub struct ProxyConfig {
+    base_url: Url,
+    additional_headers: HeaderMap,
+    removed_headers: Vec<HeaderName>,
+    prefix_rewrite: Option<(String, String)>,
+}
+
+impl ProxyConfig {
+    pub fn new<T>(base_url: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::try_new(base_url).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new<T>(base_url: T) -> Result<Self, SpoofError>
+    where
+        T: AsRef<str>,
+    {
+        let base_url = Url::parse(base_url.as_ref()).map_err(|e| SpoofError::InvalidUrl {
+            field: "base_url".to_string(),
+            value: format!("{:?}", base_url.as_ref()),
+            reason: e.to_string(),
+        })?;
+
+        Ok(Self {
+            base_url,
+            additional_headers: HeaderMap::new(),
+            removed_headers: Vec::new(),
+            prefix_rewrite: None,
+        })
+    }
+
+    pub fn with_header<K, V>(self, key: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
+    {
+        self.try_with_header(key, value)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_with_header<K, V>(mut self, key: K, value: V) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
+    {
+        self.additional_headers
+            .append(header_name(key)?, header_value(value)?);
+        Ok(self)
+    }
+
+    pub fn without_header<K>(self, key: K) -> Self
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+    {
+        self.try_without_header(key)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_without_header<K>(mut self, key: K) -> Result<Self, SpoofError>
+    where
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+    {
+        self.removed_headers.push(header_name(key)?);
+        Ok(self)
+    }
+
+    pub fn with_prefix_rewrite<F, T>(mut self, from: F, to: T) -> Self
+    where
+        F: Into<String>,
+        T: Into<String>,
+    {
+        self.prefix_rewrite = Some((from.into(), to.into()));
+        self
+    }
+
+    pub(crate) fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    pub(crate) fn additional_headers(&self) -> &HeaderMap {
+        &self.additional_headers
+    }
+
+    pub(crate) fn removed_headers(&self) -> &[HeaderName] {
+        &self.removed_headers
+    }
+
+    pub(crate) fn prefix_rewrite(&self) -> Option<(&str, &str)> {
+        self.prefix_rewrite
+            .as_ref()
+            .map(|(from, to)| (from.as_str(), to.as_str()))
+    }
+
+    pub(crate) fn upstream_url(&self, request: &Request) -> Url {
+        let path = request.url.path();
+        let path = match self.prefix_rewrite.as_ref() {
+            Some((from, to)) => match strip_path_prefix(path, from) {
+                Some(rest) => format!("{}{}", to, rest),
+                None => path.to_string(),
+            },
+            None => path.to_string(),
+        };
+
+        let mut url = self.base_url.clone();
+        let base_path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&format!("{}/{}", base_path, path.trim_start_matches('/')));
+        url.set_query(request.url.query());
+        url
+    }
+
+    pub(crate) fn upstream_headers(&self, request: &Request) -> HeaderMap {
+        let mut headers = request.headers.clone();
+        strip_hop_by_hop(&mut headers);
+        headers.remove(HOST);
+        for name in &self.removed_headers {
+            headers.remove(name);
+        }
+        for (name, value) in &self.additional_headers {
+            headers.append(name, value.clone());
+        }
+        headers
+    }
+
+    pub(crate) async fn forward(
+        &self,
+        client: &ProxyClient,
+        request: &Request,
+    ) -> Response<Full<Bytes>> {
+        // The body is gone or partial, forwarding it would not match its Content-Length
+        match &request.body_status {
+            BodyStatus::Complete => {}
+            BodyStatus::Digested { .. } | BodyStatus::TooLarge { .. } => {
+                return not_forwarded(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "the body is over the size limit",
+                )
+            }
+            BodyStatus::Failed(e) => return not_forwarded(StatusCode::BAD_REQUEST, e),
+        }
+
+        let url = self.upstream_url(request);
+        let headers = self.upstream_headers(request);
+
+        let mut builder = hyper::Request::builder()
+            .method(request.method.clone())
+            .uri(url.as_str());
+        if let Some(upstream_headers) = builder.headers_mut() {
+            *upstream_headers = headers;
+        }
+
+        // Forward the body exactly as received, along with its `Content-Encoding`
+        let upstream_request = match builder.body(Full::new(Bytes::from(request.raw_body.clone())))
+        {
+            Ok(upstream_request) => upstream_request,
+            Err(e) => return bad_gateway(format!("Cannot build request to {} : {}", url, e)),
+        };
+
+        log::debug!("Proxying {} {} to {}", request.method, request.url, url);
+
+        let upstream_response = match client.request(upstream_request).await {
+            Ok(upstream_response) => upstream_response,
+            Err(e) => return bad_gateway(format!("Cannot reach {} : {}", url, e)),
+        };
+
+        let (mut parts, body) = upstream_response.into_parts();
+        let body = match body.collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => return bad_gateway(format!("Cannot read response from {} : {}", url, e)),
+        };
+        strip_hop_by_hop(&mut parts.headers);
+
+        Response::from_parts(parts, Full::new(body))
+    }
+}
+
+fn strip_path_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = path.strip_prefix(prefix)?;
+    let whole_segments = prefix.ends_with('/') || rest.is_empty() || rest.starts_with('/');
+    whole_segments.then_some(rest)
+}
+
+fn strip_hop_by_hop(headers: &mut HeaderMap) {
+    for name in &HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+}
+
+fn not_forwarded(status: StatusCode, reason: &str) -> Response<Full<Bytes>> {
+    let message = format!("Cannot proxy the request, {}", reason);
+    log::warn!("{}", message);
+    let mut response = Response::new(Full::new(Bytes::from(message)));
+    *response.status_mut() = status;
+    response
+}
+
+fn bad_gateway(message: String) -> Response<Full<Bytes>> {
+    log::error!("{}", message);
+    let mut response = Response::new(Full::new(Bytes::from(message)));
+    *response.status_mut() = StatusCode::BAD_GATEWAY;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upstream_url(proxy: &ProxyConfig, url: &str) -> String {
+        proxy.upstream_url(&Request::get(url)).to_string()
+    }
+
+    #[test]
+    fn appends_the_request_path_and_query_to_the_base_url() {
+        let proxy = ProxyConfig::new("https://example.com/base/");
+        assert_eq!(
+            upstream_url(&proxy, "http://localhost/users/1?page=2"),
+            "https://example.com/base/users/1?page=2"
+        );
+        let proxy = ProxyConfig::new("http://example.com");
+        assert_eq!(
+            upstream_url(&proxy, "http://localhost/"),
+            "http://example.com/"
+        );
+    }
+
+    #[test]
+    fn rewrites_whole_path_segments_only() {
+        let proxy = ProxyConfig::new("http://example.com").with_prefix_rewrite("/api", "/v2");
+        assert_eq!(
+            upstream_url(&proxy, "http://localhost/api/users"),
+            "http://example.com/v2/users"
+        );
+        assert_eq!(
+            upstream_url(&proxy, "http://localhost/api"),
+            "http://example.com/v2"
+        );
+        assert_eq!(
+            upstream_url(&proxy, "http://localhost/apiary"),
+            "http://example.com/apiary"
+        );
+
+        let proxy = ProxyConfig::new("http://example.com").with_prefix_rewrite("/api/", "/");
+        assert_eq!(
+            upstream_url(&proxy, "http://localhost/api/users"),
+            "http://example.com/users"
+        );
+    }
+
+    #[test]
+    fn adds_and_removes_headers() {
+        let proxy = ProxyConfig::new("http://example.com")
+            .with_header("X-Api-Key", "secret")
+            .without_header("Cookie");
+        let mut request = Request::get("http://localhost/");
+        for (name, value) in [
+            ("host", "localhost"),
+            ("cookie", "session=1"),
+            ("connection", "keep-alive"),
+            ("accept", "application/json"),
+        ] {
+            request
+                .headers
+                .insert(name, HeaderValue::from_static(value));
+        }
+
+        let headers = proxy.upstream_headers(&request);
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["x-api-key"], "secret");
+        assert_eq!(headers["accept"], "application/json");
+    }
+
+    #[tokio::test]
+    async fn refuses_bodies_that_were_not_kept() {
+        let proxy = ProxyConfig::new("http://127.0.0.1:9");
+        let mut request = Request::get("http://localhost/");
+        request.body_status = BodyStatus::Digested {
+            size: 1 << 20,
+            sha256: String::new(),
+        };
+        let response = proxy.forward(&proxy_client(), &request).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -1,7 +1,20 @@
-use bytes::Bytes;
+use crate::error::SpoofError;
+use crate::matcher::header::{header_name, header_value};
+use crate::net::request::Request;
+use crate::response::body::{full_body, ResponseBody};
+use crate::response::compression::ResponseCompression;
+use crate::response::fault::Fault;
+use crate::response::files;
+use crate::response::proxy::ProxyConfig;
+use crate::response::templating::{template_model, TemplateEngine};
+use http::header::CONTENT_TYPE;
 use http::{HeaderMap, HeaderName, HeaderValue, Response, StatusCode};
-use http_body_util::Full;
 use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
 #[derive(Clone, Debug)]
@@ -10,7 +23,26 @@ pub struct MockResponseBuilder {
     status_code: Option<StatusCode>,
     headers: HeaderMap,
     body: Option<Vec<u8>>,
+    body_file: Option<PathBuf>,
     delay: Option<Duration>,
+    compression: Option<ResponseCompression>,
+    templated: bool,
+    status_template: Option<String>,
+    proxy: Option<ProxyConfig>,
+    fault: Option<Fault>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ResponseSettings {
+    pub compression: ResponseCompression,
+    pub templates: Arc<TemplateEngine>,
+    pub files_root: PathBuf,
+}
+
+impl Default for MockResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MockResponseBuilder {
@@ -20,96 +52,99 @@ impl MockResponseBuilder {
             status_code: None,
             headers: HeaderMap::new(),
             body: None,
+            body_file: None,
             delay: None,
+            compression: None,
+            templated: false,
+            status_template: None,
+            proxy: None,
+            fault: None,
         }
     }
 
-    pub fn with_status_code<S>(mut self, status_code: S) -> Self
+    pub fn try_with_status_code<S>(mut self, status_code: S) -> Result<Self, SpoofError>
     where
-        S: TryInto<StatusCode>,
-        <S as TryInto<StatusCode>>::Error: std::fmt::Debug,
+        S: TryInto<StatusCode> + Debug,
+        <S as TryInto<StatusCode>>::Error: Display,
     {
+        let value = format!("{:?}", status_code);
         let status_code = status_code
             .try_into()
-            .expect("Cannot convert field to HTTP Status Code");
+            .map_err(|e| SpoofError::InvalidStatusCode {
+                field: "status".to_string(),
+                value,
+                reason: e.to_string(),
+            })?;
         self.status_code = Some(status_code);
-        self
+        Ok(self)
     }
 
-    pub fn with_header_append<K, V>(mut self, key: K, value: V) -> Self
+    pub fn try_with_header_append<K, V>(mut self, key: K, value: V) -> Result<Self, SpoofError>
     where
-        K: TryInto<HeaderName>,
-        <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-        V: TryInto<HeaderValue>,
-        <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+        K: TryInto<HeaderName> + Debug,
+        <K as TryInto<HeaderName>>::Error: Display,
+        V: TryInto<HeaderValue> + Debug,
+        <V as TryInto<HeaderValue>>::Error: Display,
     {
-        let key = key.try_into().expect("Cannot convert field to header name");
-        let value = value
-            .try_into()
-            .expect("Cannot convert field to header value");
-        self.headers.append(key, value);
-        self
+        self.headers.append(header_name(key)?, header_value(value)?);
+        Ok(self)
     }
 
-    pub fn with_header_insert<K, V>(mut self, key: K, value: V) -> Self
+    pub fn with_body_bytes<B>(self, body: B) -> Self
     where
-        K: TryInto<HeaderName>,
-        <K as TryInto<HeaderName>>::Error: std::fmt::Debug,
-        V: TryInto<HeaderValue>,
-        <V as TryInto<HeaderValue>>::Error: std::fmt::Debug,
+        B: TryInto<Vec<u8>>,
+        <B as TryInto<Vec<u8>>>::Error: Display,
     {
-        let key = key.try_into().expect("Cannot convert field to header name");
-        let value = value
-            .try_into()
-            .expect("Cannot convert field to header value");
-        self.headers.insert(key, value);
-        self
+        self.try_with_body_bytes(body)
+            .unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn with_body_bytes<B>(mut self, body: B) -> Self
+    pub fn try_with_body_bytes<B>(mut self, body: B) -> Result<Self, SpoofError>
     where
         B: TryInto<Vec<u8>>,
-        <B as TryInto<Vec<u8>>>::Error: std::fmt::Debug,
+        <B as TryInto<Vec<u8>>>::Error: Display,
     {
-        let body = body.try_into().expect("Cannot convert body to byte stream");
-        self.body = Some(body);
-        self
+        self.body = Some(body.try_into().map_err(invalid_body)?);
+        Ok(self)
     }
 
-    pub fn with_body_json<S>(mut self, body: S) -> Self
+    pub fn try_with_body_json<S>(mut self, body: S) -> Result<Self, SpoofError>
     where
         S: Serialize,
     {
-        let body = serde_json::to_vec(&body).expect("Cannot deserialize body to byte stream");
+        let body = serde_json::to_vec(&body).map_err(invalid_body)?;
 
         self.body = Some(body);
         self.mime_type = "application/json".to_string();
-        self
+        Ok(self)
     }
 
-    pub fn with_body_string<T>(mut self, body: T) -> Self
+    pub fn with_body_string<T>(self, body: T) -> Self
     where
         T: TryInto<String>,
-        <T as TryInto<String>>::Error: std::fmt::Debug,
+        <T as TryInto<String>>::Error: Display,
     {
-        let body = body.try_into().expect("Cannot convert field to String");
+        self.try_with_body_string(body)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_with_body_string<T>(mut self, body: T) -> Result<Self, SpoofError>
+    where
+        T: TryInto<String>,
+        <T as TryInto<String>>::Error: Display,
+    {
+        let body = body.try_into().map_err(invalid_body)?;
 
         self.body = Some(body.into_bytes());
         self.mime_type = "text/plain".to_string();
-        self
+        Ok(self)
     }
 
-    pub fn with_body<T>(mut self, body: T, mime_type: &str) -> Self
+    pub fn with_body_file<P>(mut self, path: P) -> Self
     where
-        T: TryInto<Vec<u8>>,
-        <T as TryInto<Vec<u8>>>::Error: std::fmt::Debug,
+        P: Into<PathBuf>,
     {
-        let body = body
-            .try_into()
-            .expect("Cannot convert field to byte stream");
-
-        self.body = Some(body);
-        self.mime_type = mime_type.to_string();
+        self.body_file = Some(path.into());
         self
     }
 
@@ -118,20 +153,183 @@ impl MockResponseBuilder {
         self
     }
 
-    pub(crate) fn build_response(&self) -> Response<Full<Bytes>> {
-        let status_code = self.status_code.unwrap_or_else(|| StatusCode::OK);
+    pub fn with_templating(mut self) -> Self {
+        self.templated = true;
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn with_fault(mut self, fault: Fault) -> Self {
+        self.fault = Some(fault);
+        self
+    }
+
+    pub(crate) fn proxy(&self) -> Option<&ProxyConfig> {
+        self.proxy.as_ref()
+    }
+
+    pub(crate) fn delay(&self) -> Option<Duration> {
+        self.delay
+    }
+
+    pub(crate) fn fault(&self) -> Option<Fault> {
+        self.fault
+    }
+
+    pub(crate) fn status_code(&self) -> Option<StatusCode> {
+        self.status_code
+    }
+
+    pub(crate) fn headers(&self) -> HeaderMap {
         let mut headers = self.headers.clone();
+        if !self.mime_type.is_empty() && !headers.contains_key(CONTENT_TYPE) {
+            if let Ok(mime_type) = self.mime_type.parse() {
+                headers.insert(CONTENT_TYPE, mime_type);
+            }
+        }
+        headers
+    }
+
+    pub(crate) fn body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+
+    pub(crate) fn body_file(&self) -> Option<&Path> {
+        self.body_file.as_deref()
+    }
+
+    pub(crate) fn is_templated(&self) -> bool {
+        self.templated
+    }
+
+    pub(crate) async fn build_response(
+        &self,
+        request: &Request,
+        path_parameters: &HashMap<String, String>,
+        settings: &ResponseSettings,
+    ) -> Response<ResponseBody> {
+        let mut status_code = self.status_code.unwrap_or(StatusCode::OK);
+        let mut headers = self.headers();
+
+        let mut body = self.body.clone().unwrap_or_default();
+
+        if let Some(body_file) = self.body_file.as_ref() {
+            let Some(path) = files::resolve(&settings.files_root, body_file) else {
+                let e = std::io::Error::other("path escapes the files root");
+                return files::missing_file(body_file, e);
+            };
+            if !headers.contains_key(CONTENT_TYPE) {
+                if let Some(content_type) = files::content_type(&path) {
+                    headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+                }
+            }
+
+            let size = match tokio::fs::metadata(&path).await {
+                Ok(metadata) => metadata.len(),
+                Err(e) => return files::missing_file(&path, e),
+            };
+            // Large fixtures go out as they are, without templating or compression
+            if !self.templated && size > files::STREAMING_THRESHOLD {
+                return match files::stream_file(&path, status_code, headers).await {
+                    Ok(response) => response,
+                    Err(e) => files::missing_file(&path, e),
+                };
+            }
+            body = match tokio::fs::read(&path).await {
+                Ok(body) => body,
+                Err(e) => return files::missing_file(&path, e),
+            };
+        }
+
+        if self.templated {
+            let model = template_model(request, path_parameters);
+            let templates = &settings.templates;
+
+            if let Some(template) = self.status_template.as_deref() {
+                match render(templates, template, &model).map(|it| it.trim().parse::<u16>()) {
+                    Ok(Ok(rendered)) => match StatusCode::from_u16(rendered) {
+                        Ok(rendered) => status_code = rendered,
+                        Err(e) => log::error!("Cannot use {} as status code : {}", rendered, e),
+                    },
+                    Ok(Err(e)) => log::error!("Rendered status template is not a number : {}", e),
+                    Err(e) => log::error!("{}", e),
+                }
+            }
 
-        if !self.mime_type.is_empty() {
-            headers.insert(http::header::CONTENT_TYPE, self.mime_type.parse().unwrap());
+            for value in headers.values_mut() {
+                if let Some(rendered) = value
+                    .to_str()
+                    .ok()
+                    .and_then(|template| render(templates, template, &model).ok())
+                    .and_then(|rendered| HeaderValue::from_str(&rendered).ok())
+                {
+                    *value = rendered;
+                }
+            }
+
+            if let Ok(template) = std::str::from_utf8(&body) {
+                match render(templates, template, &model) {
+                    Ok(rendered) => body = rendered.into_bytes(),
+                    Err(e) => log::error!("{}", e),
+                }
+            }
         }
 
-        let body = self.body.clone().unwrap_or_default();
+        let body = self
+            .compression
+            .as_ref()
+            .unwrap_or(&settings.compression)
+            .apply(&request.headers, &mut headers, body);
 
         let mut response_builder = Response::builder().status(status_code);
 
         *response_builder.headers_mut().unwrap() = headers;
 
-        response_builder.body(body.into()).unwrap()
+        response_builder.body(full_body(body)).unwrap()
+    }
+}
+
+fn render(templates: &TemplateEngine, template: &str, model: &Value) -> Result<String, String> {
+    // Skip the engine for values that cannot hold an expression
+    if template.contains("{{") {
+        templates.render(template, model)
+    } else {
+        Ok(template.to_string())
+    }
+}
+
+fn invalid_body<E: Display>(error: E) -> SpoofError {
+    SpoofError::InvalidBody {
+        field: "body".to_string(),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn content_type(builder: MockResponseBuilder) -> Option<HeaderValue> {
+        let request = Request::get("http://localhost/");
+        let response = builder
+            .build_response(&request, &HashMap::new(), &ResponseSettings::default())
+            .await;
+        response.headers().get(CONTENT_TYPE).cloned()
+    }
+
+    #[tokio::test]
+    async fn body_methods_keep_the_configured_content_type() {
+        let builder = MockResponseBuilder::new()
+            .try_with_header_append("Content-Type", "application/xml")
+            .unwrap()
+            .with_body_string("<order/>");
+        assert_eq!(content_type(builder).await.unwrap(), "application/xml");
+
+        let builder = MockResponseBuilder::new().with_body_string("plain");
+        assert_eq!(content_type(builder).await.unwrap(), "text/plain");
     }
 }
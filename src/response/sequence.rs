@@ -0,0 +1,113 @@
+use crate::response::response_template::MockResponseBuilder;
+use crate::response::templating::SharedRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SequencePolicy {
+    #[default]
+    StickOnLast,
+    Loop,
+    Random,
+}
+
+#[derive(Debug)]
+pub struct ResponseSequence {
+    responses: Vec<(MockResponseBuilder, u32)>,
+    policy: SequencePolicy,
+    served: AtomicUsize,
+}
+
+impl ResponseSequence {
+    pub fn new(responses: Vec<(MockResponseBuilder, u32)>, policy: SequencePolicy) -> Self {
+        let responses = if responses.is_empty() {
+            vec![(MockResponseBuilder::new(), 1)]
+        } else {
+            responses
+        };
+
+        Self {
+            responses,
+            policy,
+            served: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn next_response(&self, rng: &SharedRng) -> &MockResponseBuilder {
+        let count = self.responses.len();
+        let index = match self.policy {
+            SequencePolicy::StickOnLast => {
+                self.served.fetch_add(1, Ordering::Relaxed).min(count - 1)
+            }
+            SequencePolicy::Loop => self.served.fetch_add(1, Ordering::Relaxed) % count,
+            SequencePolicy::Random => self.random_index(rng),
+        };
+        &self.responses[index].0
+    }
+
+    fn random_index(&self, rng: &SharedRng) -> usize {
+        let mut rng = rng.lock().expect("Template RNG lock poisoned");
+        let total = self
+            .responses
+            .iter()
+            .map(|(_, weight)| u64::from(*weight))
+            .sum::<u64>();
+        if total == 0 {
+            return rng.random_range(0..self.responses.len());
+        }
+
+        let mut remaining = rng.random_range(0..total);
+        for (index, (_, weight)) in self.responses.iter().enumerate() {
+            let weight = u64::from(*weight);
+            if remaining < weight {
+                return index;
+            }
+            remaining -= weight;
+        }
+        self.responses.len() - 1
+    }
+}
+
+impl From<MockResponseBuilder> for ResponseSequence {
+    fn from(response: MockResponseBuilder) -> Self {
+        ResponseSequence::new(vec![(response, 1)], SequencePolicy::StickOnLast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::{Arc, Mutex};
+
+    fn seeded(seed: u64) -> SharedRng {
+        Arc::new(Mutex::new(StdRng::seed_from_u64(seed)))
+    }
+
+    fn picks(sequence: &ResponseSequence, rng: &SharedRng) -> Vec<usize> {
+        (0..32).map(|_| sequence.random_index(rng)).collect()
+    }
+
+    fn sequence(weights: &[u32]) -> ResponseSequence {
+        let responses = weights
+            .iter()
+            .map(|weight| (MockResponseBuilder::new(), *weight))
+            .collect();
+        ResponseSequence::new(responses, SequencePolicy::Random)
+    }
+
+    #[test]
+    fn random_picks_follow_the_seed() {
+        let sequence = sequence(&[1, 1, 1, 1]);
+        assert_eq!(picks(&sequence, &seeded(7)), picks(&sequence, &seeded(7)));
+    }
+
+    #[test]
+    fn zero_weight_responses_are_never_picked() {
+        let sequence = sequence(&[0, 1, 0]);
+        assert!(picks(&sequence, &seeded(7)).iter().all(|it| *it == 1));
+    }
+}
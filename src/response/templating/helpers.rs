@@ -0,0 +1,523 @@
+use super::SharedRng;
+use base64::prelude::{BASE64_STANDARD, BASE64_STANDARD_NO_PAD};
+use base64::Engine;
+use chrono::{DateTime, Duration, Months, SecondsFormat, Utc};
+use handlebars::{
+    Context, Handlebars, Helper, HelperDef, RenderContext, RenderError, RenderErrorReason,
+    ScopedJson,
+};
+use rand::Rng;
+use regex::Regex;
+use serde_json::{Number, Value};
+use serde_json_path::JsonPath;
+use std::fmt::Write;
+use std::sync::Arc;
+
+const ALPHABETIC: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NUMERIC: &str = "0123456789";
+const HEXADECIMAL: &str = "0123456789abcdef";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>/?";
+const MAX_GENERATED_LENGTH: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Clock {
+    #[default]
+    System,
+    Fixed(DateTime<Utc>),
+}
+
+impl Clock {
+    fn now(&self) -> DateTime<Utc> {
+        match self {
+            Clock::System => Utc::now(),
+            Clock::Fixed(now) => *now,
+        }
+    }
+}
+
+pub(crate) fn register(handlebars: &mut Handlebars, clock: Clock, rng: SharedRng) {
+    handlebars.register_helper("jsonPath", Box::new(ValueHelper(json_path_helper)));
+    handlebars.register_helper("xPath", Box::new(ValueHelper(x_path_helper)));
+    handlebars.register_helper("regexExtract", Box::new(ValueHelper(regex_extract_helper)));
+    handlebars.register_helper("base64", Box::new(ValueHelper(base64_helper)));
+    handlebars.register_helper("urlEncode", Box::new(ValueHelper(url_encode_helper)));
+    handlebars.register_helper("math", Box::new(ValueHelper(math_helper)));
+    handlebars.register_helper("range", Box::new(ValueHelper(range_helper)));
+    handlebars.register_helper("array", Box::new(ValueHelper(array_helper)));
+    handlebars.register_helper("contains", Box::new(ValueHelper(contains_helper)));
+    handlebars.register_helper("matches", Box::new(ValueHelper(matches_helper)));
+    handlebars.register_helper(
+        "now",
+        Box::new(ValueHelper(move |helper: &Helper| {
+            now_helper(helper, &clock)
+        })),
+    );
+    let random_rng = Arc::clone(&rng);
+    handlebars.register_helper(
+        "randomValue",
+        Box::new(ValueHelper(move |helper: &Helper| {
+            random_value_helper(helper, &random_rng)
+        })),
+    );
+    handlebars.register_helper(
+        "pickRandom",
+        Box::new(ValueHelper(move |helper: &Helper| {
+            pick_random_helper(helper, &rng)
+        })),
+    );
+}
+
+struct ValueHelper<F>(F);
+
+impl<F> HelperDef for ValueHelper<F>
+where
+    F: Fn(&Helper) -> Result<Value, RenderError> + Send + Sync,
+{
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        helper: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        (self.0)(helper).map(ScopedJson::Derived)
+    }
+}
+
+fn param<'a>(
+    helper: &'a Helper,
+    name: &'static str,
+    index: usize,
+) -> Result<&'a Value, RenderError> {
+    helper
+        .param(index)
+        .map(|param| param.value())
+        .ok_or_else(|| RenderErrorReason::ParamNotFoundForIndex(name, index).into())
+}
+
+fn str_param<'a>(
+    helper: &'a Helper,
+    name: &'static str,
+    index: usize,
+) -> Result<&'a str, RenderError> {
+    param(helper, name, index)?
+        .as_str()
+        .ok_or_else(|| RenderErrorReason::InvalidParamType("string").into())
+}
+
+fn hash_str<'a>(helper: &'a Helper, key: &str) -> Option<&'a str> {
+    helper
+        .hash_get(key)
+        .and_then(|value| value.value().as_str())
+}
+
+fn hash_bool(helper: &Helper, key: &str) -> bool {
+    helper
+        .hash_get(key)
+        .map(|value| match value.value() {
+            Value::Bool(value) => *value,
+            Value::String(value) => value.eq_ignore_ascii_case("true"),
+            _ => false,
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn render_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn json_path_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let document = match param(helper, "jsonPath", 0)? {
+        Value::String(json) => match serde_json::from_str::<Value>(json) {
+            Ok(document) => document,
+            Err(_) => return Ok(Value::Null),
+        },
+        other => other.clone(),
+    };
+    let expression = str_param(helper, "jsonPath", 1)?;
+    let path = JsonPath::parse(expression).map_err(|e| {
+        RenderErrorReason::Other(format!("Invalid JSONPath {} : {}", expression, e))
+    })?;
+
+    Ok(path.query(&document).first().cloned().unwrap_or_default())
+}
+
+fn x_path_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let document = str_param(helper, "xPath", 0)?;
+    let expression = str_param(helper, "xPath", 1)?;
+
+    let package = match sxd_document::parser::parse(document) {
+        Ok(package) => package,
+        Err(_) => return Ok(Value::Null),
+    };
+    let document = package.as_document();
+    let value = sxd_xpath::evaluate_xpath(&document, expression)
+        .map_err(|e| RenderErrorReason::Other(format!("Invalid XPath {} : {}", expression, e)))?;
+
+    Ok(match value {
+        sxd_xpath::Value::Nodeset(nodes) => nodes
+            .document_order_first()
+            .map(|node| Value::from(node.string_value()))
+            .unwrap_or_default(),
+        sxd_xpath::Value::Boolean(value) => Value::from(value),
+        sxd_xpath::Value::Number(value) => Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or_default(),
+        sxd_xpath::Value::String(value) => Value::from(value),
+    })
+}
+
+fn regex_extract_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let value = render_value(param(helper, "regexExtract", 0)?);
+    let pattern = str_param(helper, "regexExtract", 1)?;
+    let regex = Regex::new(pattern)
+        .map_err(|e| RenderErrorReason::Other(format!("Invalid regex {} : {}", pattern, e)))?;
+    let group = helper
+        .hash_get("group")
+        .and_then(|group| group.value().as_u64())
+        .unwrap_or_default() as usize;
+
+    Ok(regex
+        .captures(&value)
+        .and_then(|captures| captures.get(group))
+        .map(|found| Value::from(found.as_str()))
+        .unwrap_or_default())
+}
+
+fn base64_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let value = render_value(param(helper, "base64", 0)?);
+    let engine = if helper.hash_get("padding").map(|it| it.value()) == Some(&Value::Bool(false)) {
+        &BASE64_STANDARD_NO_PAD
+    } else {
+        &BASE64_STANDARD
+    };
+
+    if hash_bool(helper, "decode") {
+        let decoded = BASE64_STANDARD
+            .decode(value.trim_end_matches('='))
+            .or_else(|_| BASE64_STANDARD_NO_PAD.decode(value.trim_end_matches('=')))
+            .map_err(|e| RenderErrorReason::Other(format!("Invalid base64 {} : {}", value, e)))?;
+        Ok(Value::from(String::from_utf8_lossy(&decoded).to_string()))
+    } else {
+        Ok(Value::from(engine.encode(value)))
+    }
+}
+
+fn url_encode_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let value = render_value(param(helper, "urlEncode", 0)?);
+
+    if hash_bool(helper, "decode") {
+        Ok(Value::from(
+            url::form_urlencoded::parse(format!("v={}", value).as_bytes())
+                .next()
+                .map(|(_, decoded)| decoded.to_string())
+                .unwrap_or_default(),
+        ))
+    } else {
+        Ok(Value::from(
+            url::form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>(),
+        ))
+    }
+}
+
+fn as_number(value: &Value) -> Option<Number> {
+    match value {
+        Value::Number(number) => Some(number.clone()),
+        Value::String(value) => serde_json::from_str::<Number>(value.trim()).ok(),
+        _ => None,
+    }
+}
+
+fn math_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let invalid = || RenderError::from(RenderErrorReason::InvalidParamType("number"));
+    let left = as_number(param(helper, "math", 0)?).ok_or_else(invalid)?;
+    let operator = str_param(helper, "math", 1)?;
+    let right = as_number(param(helper, "math", 2)?).ok_or_else(invalid)?;
+
+    if let (Some(left), Some(right)) = (left.as_i64(), right.as_i64()) {
+        let result = match operator {
+            "+" => left.checked_add(right),
+            "-" => left.checked_sub(right),
+            "*" => left.checked_mul(right),
+            "/" if left.checked_rem(right) == Some(0) => left.checked_div(right),
+            "%" => left.checked_rem(right),
+            _ => None,
+        };
+        if let Some(result) = result {
+            return Ok(Value::from(result));
+        }
+    }
+
+    let (left, right) = (
+        left.as_f64().ok_or_else(invalid)?,
+        right.as_f64().ok_or_else(invalid)?,
+    );
+    let result = match operator {
+        "+" => left + right,
+        "-" => left - right,
+        "*" => left * right,
+        "/" => left / right,
+        "%" => left % right,
+        other => {
+            return Err(
+                RenderErrorReason::Other(format!("Unsupported math operator {}", other)).into(),
+            )
+        }
+    };
+    Ok(Number::from_f64(result)
+        .map(Value::Number)
+        .unwrap_or_default())
+}
+
+fn range_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let bound = |index| {
+        as_number(param(helper, "range", index)?)
+            .and_then(|number| number.as_i64())
+            .ok_or_else(|| RenderError::from(RenderErrorReason::InvalidParamType("integer")))
+    };
+    let (start, end) = (bound(0)?, bound(1)?);
+    if start.abs_diff(end) >= MAX_GENERATED_LENGTH {
+        return Err(too_long("range", MAX_GENERATED_LENGTH));
+    }
+
+    Ok(if start <= end {
+        (start..=end).map(Value::from).collect()
+    } else {
+        (end..=start).rev().map(Value::from).collect()
+    })
+}
+
+fn array_helper(helper: &Helper) -> Result<Value, RenderError> {
+    Ok(Value::Array(
+        helper
+            .params()
+            .iter()
+            .map(|param| param.value().clone())
+            .collect(),
+    ))
+}
+
+fn contains_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let needle = param(helper, "contains", 1)?;
+    Ok(Value::from(match param(helper, "contains", 0)? {
+        Value::Array(values) => values.contains(needle),
+        haystack => render_value(haystack).contains(&render_value(needle)),
+    }))
+}
+
+fn matches_helper(helper: &Helper) -> Result<Value, RenderError> {
+    let value = render_value(param(helper, "matches", 0)?);
+    let pattern = str_param(helper, "matches", 1)?;
+    let regex = Regex::new(pattern)
+        .map_err(|e| RenderErrorReason::Other(format!("Invalid regex {} : {}", pattern, e)))?;
+    Ok(Value::from(regex.is_match(&value)))
+}
+
+fn now_helper(helper: &Helper, clock: &Clock) -> Result<Value, RenderError> {
+    let mut now = clock.now();
+
+    if let Some(offset) = hash_str(helper, "offset") {
+        now = apply_offset(now, offset)
+            .ok_or_else(|| RenderErrorReason::Other(format!("Invalid date offset {}", offset)))?;
+    }
+
+    Ok(match hash_str(helper, "format") {
+        None => Value::from(now.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        Some("epoch") => Value::from(now.timestamp_millis()),
+        Some("unix") => Value::from(now.timestamp()),
+        Some(format) => {
+            let mut formatted = String::new();
+            write!(formatted, "{}", now.format(format))
+                .map_err(|_| RenderErrorReason::Other(format!("Invalid date format {}", format)))?;
+            Value::from(formatted)
+        }
+    })
+}
+
+fn apply_offset(now: DateTime<Utc>, offset: &str) -> Option<DateTime<Utc>> {
+    let mut parts = offset.split_whitespace();
+    let amount = parts.next()?.trim_start_matches('+').parse::<i64>().ok()?;
+    let unit = parts.next()?.to_ascii_lowercase();
+    let unit = unit.trim_end_matches('s');
+
+    let months = |months: i64| {
+        let magnitude = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+        if months < 0 {
+            now.checked_sub_months(magnitude)
+        } else {
+            now.checked_add_months(magnitude)
+        }
+    };
+
+    match unit {
+        "millisecond" => now.checked_add_signed(Duration::try_milliseconds(amount)?),
+        "second" => now.checked_add_signed(Duration::try_seconds(amount)?),
+        "minute" => now.checked_add_signed(Duration::try_minutes(amount)?),
+        "hour" => now.checked_add_signed(Duration::try_hours(amount)?),
+        "day" => now.checked_add_signed(Duration::try_days(amount)?),
+        "week" => now.checked_add_signed(Duration::try_weeks(amount)?),
+        "month" => months(amount),
+        "year" => months(amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+fn too_long(helper: &str, max_length: u64) -> RenderError {
+    RenderErrorReason::Other(format!(
+        "{} cannot produce more than {} elements",
+        helper, max_length
+    ))
+    .into()
+}
+
+fn random_value_helper(helper: &Helper, rng: &SharedRng) -> Result<Value, RenderError> {
+    let kind = hash_str(helper, "type").unwrap_or("ALPHANUMERIC");
+    let length = helper
+        .hash_get("length")
+        .and_then(|length| as_number(length.value()))
+        .and_then(|length| length.as_u64())
+        .unwrap_or(10);
+    if length > MAX_GENERATED_LENGTH {
+        return Err(too_long("randomValue", MAX_GENERATED_LENGTH));
+    }
+    let mut rng = rng.lock().expect("Template RNG lock poisoned");
+
+    let alphabet = match kind {
+        "UUID" => {
+            let uuid = uuid::Builder::from_random_bytes(rng.random()).into_uuid();
+            return Ok(Value::from(uuid.to_string()));
+        }
+        "ALPHANUMERIC" => format!("{}{}", ALPHABETIC, NUMERIC),
+        "ALPHABETIC" => ALPHABETIC.to_string(),
+        "NUMERIC" => NUMERIC.to_string(),
+        "HEXADECIMAL" => HEXADECIMAL.to_string(),
+        "ALPHANUMERIC_AND_SYMBOLS" => format!("{}{}{}", ALPHABETIC, NUMERIC, SYMBOLS),
+        other => {
+            return Err(
+                RenderErrorReason::Other(format!("Unknown random value type {}", other)).into(),
+            )
+        }
+    };
+    let alphabet = alphabet.chars().collect::<Vec<_>>();
+    let value = (0..length)
+        .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+        .collect::<String>();
+
+    Ok(Value::from(if hash_bool(helper, "uppercase") {
+        value.to_uppercase()
+    } else {
+        value
+    }))
+}
+
+fn pick_random_helper(helper: &Helper, rng: &SharedRng) -> Result<Value, RenderError> {
+    let candidates = match helper.params().as_slice() {
+        [single] if single.value().is_array() => {
+            single.value().as_array().cloned().unwrap_or_default()
+        }
+        params => params.iter().map(|param| param.value().clone()).collect(),
+    };
+    if candidates.is_empty() {
+        return Err(RenderErrorReason::ParamNotFoundForIndex("pickRandom", 0).into());
+    }
+
+    let index = rng
+        .lock()
+        .expect("Template RNG lock poisoned")
+        .random_range(0..candidates.len());
+    Ok(candidates[index].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::response::templating::{Clock, TemplateEngine, TemplateOptions};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn render(template: &str) -> Result<String, String> {
+        TemplateEngine::new().render(template, &json!({}))
+    }
+
+    fn reproducible() -> TemplateEngine {
+        TemplateEngine::with_options(TemplateOptions {
+            clock: Clock::Fixed(Utc.with_ymd_and_hms(2024, 2, 28, 12, 30, 0).unwrap()),
+            seed: Some(42),
+        })
+    }
+
+    #[test]
+    fn seeded_random_helpers_are_reproducible() {
+        let template =
+            "{{randomValue length=16 type='ALPHANUMERIC'}} {{pickRandom 'a' 'b' 'c' 'd'}} \
+                        {{pickRandom (array 1 2 3 4)}} {{randomValue type='UUID'}}";
+        let first = reproducible().render(template, &json!({})).unwrap();
+        let second = reproducible().render(template, &json!({})).unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, render(template).unwrap());
+    }
+
+    #[test]
+    fn fixed_clock_renders_fixed_dates() {
+        let engine = reproducible();
+        let render = |template| engine.render(template, &json!({})).unwrap();
+        assert_eq!(
+            render("{{now format='%Y-%m-%d %H:%M'}}"),
+            "2024-02-28 12:30"
+        );
+        assert_eq!(
+            render("{{now offset='2 days' format='%Y-%m-%d'}}"),
+            "2024-03-01"
+        );
+        assert_eq!(
+            render("{{now offset='-30 minutes' format='%H:%M'}}"),
+            "12:00"
+        );
+        assert_eq!(render("{{now format='epoch'}}"), "1709123400000");
+    }
+
+    #[test]
+    fn math_does_not_overflow() {
+        assert_eq!(render("{{math 7 '/' 2}}").unwrap(), "3.5");
+        assert_eq!(render("{{math 7 '%' 0}}").unwrap(), "");
+        assert_eq!(
+            render("{{math -9223372036854775808 '%' -1}}").unwrap(),
+            "-0.0"
+        );
+        assert_eq!(
+            render("{{math -9223372036854775808 '/' -1}}").unwrap(),
+            "9.223372036854776e+18"
+        );
+    }
+
+    #[test]
+    fn range_is_inclusive_in_both_directions() {
+        assert_eq!(
+            render("{{#each (range 1 3)}}{{this}}{{/each}}").unwrap(),
+            "123"
+        );
+        assert_eq!(
+            render("{{#each (range 3 1)}}{{this}}{{/each}}").unwrap(),
+            "321"
+        );
+    }
+
+    #[test]
+    fn range_is_capped() {
+        assert!(render("{{#each (range 1 10000)}}{{/each}}").is_ok());
+        assert!(render("{{#each (range 0 10000)}}{{/each}}").is_err());
+        assert!(render("{{#each (range -1000000000 1000000000)}}{{/each}}").is_err());
+    }
+
+    #[test]
+    fn random_value_length_is_capped() {
+        assert_eq!(
+            render("{{randomValue length=10000}}").unwrap().len(),
+            10_000
+        );
+        assert!(render("{{randomValue length=10001}}").is_err());
+    }
+}
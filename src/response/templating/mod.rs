@@ -0,0 +1,151 @@
+mod helpers;
+
+pub use helpers::Clock;
+
+use crate::net::request::Request;
+use handlebars::Handlebars;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+pub(crate) type SharedRng = Arc<Mutex<StdRng>>;
+
+pub struct TemplateEngine {
+    handlebars: Handlebars<'static>,
+    rng: SharedRng,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TemplateOptions {
+    pub clock: Clock,
+    pub seed: Option<u64>,
+}
+
+impl TemplateEngine {
+    pub fn new() -> Self {
+        Self::with_options(TemplateOptions::default())
+    }
+
+    pub fn with_options(options: TemplateOptions) -> Self {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_escape_fn(handlebars::no_escape);
+        let rng = Arc::new(Mutex::new(match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        }));
+        helpers::register(&mut handlebars, options.clock, Arc::clone(&rng));
+        Self { handlebars, rng }
+    }
+
+    pub(crate) fn rng(&self) -> &SharedRng {
+        &self.rng
+    }
+
+    pub(crate) fn render(&self, template: &str, model: &Value) -> Result<String, String> {
+        self.handlebars
+            .render_template(template, model)
+            .map_err(|e| format!("Cannot render response template : {}", e))
+    }
+}
+
+impl Default for TemplateEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for TemplateEngine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TemplateEngine").finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn template_model(
+    request: &Request,
+    path_parameters: &HashMap<String, String>,
+) -> Value {
+    let mut path = Map::new();
+    request
+        .url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .enumerate()
+        .for_each(|(index, segment)| {
+            path.insert(index.to_string(), Value::from(segment));
+        });
+    path_parameters.iter().for_each(|(name, value)| {
+        path.insert(name.clone(), Value::from(value.as_str()));
+    });
+
+    let mut query = HashMap::<String, Vec<String>>::new();
+    request.url.query_pairs().for_each(|(key, value)| {
+        query
+            .entry(key.to_string())
+            .or_default()
+            .push(value.to_string())
+    });
+
+    let mut headers = HashMap::<String, Vec<String>>::new();
+    request.headers.iter().for_each(|(name, value)| {
+        let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+        headers
+            .entry(name.as_str().to_string())
+            .or_default()
+            .push(value.clone());
+        let canonical = canonical_header_name(name.as_str());
+        if canonical != name.as_str() {
+            headers.entry(canonical).or_default().push(value);
+        }
+    });
+
+    let url = match request.url.query() {
+        Some(query) => format!("{}?{}", request.url.path(), query),
+        None => request.url.path().to_string(),
+    };
+
+    json!({
+        "request": {
+            "url": url,
+            "method": request.method.as_str(),
+            "scheme": request.url.scheme(),
+            "host": request.url.host_str(),
+            "port": request.url.port_or_known_default(),
+            "path": path,
+            "query": multi_values(query),
+            "headers": multi_values(headers),
+            "body": String::from_utf8_lossy(&request.body),
+        }
+    })
+}
+
+fn multi_values(values: HashMap<String, Vec<String>>) -> Map<String, Value> {
+    values
+        .into_iter()
+        .map(|(key, mut values)| {
+            let value = if values.len() == 1 {
+                Value::from(values.remove(0))
+            } else {
+                Value::from(values)
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+fn canonical_header_name(name: &str) -> String {
+    name.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
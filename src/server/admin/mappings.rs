@@ -0,0 +1,213 @@
+use crate::core::mock::{FileSource, Mock};
+use crate::matcher::body::BodyJsonMatcher;
+use crate::model::mapping::files::write_mapping_file;
+use crate::model::mapping::validation::{validate, MappingError};
+use crate::model::mapping::StubMappingStub;
+use crate::model::request::body::BodyPatternStub;
+use crate::net::request::Request;
+use crate::server::admin::json_response;
+use crate::server::config::MAPPINGS_DIR;
+use crate::server::mappings::load_file_mappings;
+use crate::server::ServerContext;
+use bytes::Bytes;
+use http::StatusCode;
+use http_body_util::Full;
+use hyper::Response;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+
+pub(super) fn create_mapping(context: &ServerContext, request: &Request) -> Response<Full<Bytes>> {
+    let value = match serde_json::from_slice::<Value>(&request.body) {
+        Ok(value) => value,
+        Err(e) => return json_response(StatusCode::BAD_REQUEST, json!({ "error": e.to_string() })),
+    };
+    let errors = validate(&value, "");
+    if !errors.is_empty() {
+        return json_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            json!({ "errors": errors }),
+        );
+    }
+    let mut mapping = match serde_json::from_value::<StubMappingStub>(value) {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            return json_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                json!({ "errors": [{ "pointer": "", "message": e.to_string() }] }),
+            )
+        }
+    };
+    mapping
+        .id
+        .get_or_insert_with(|| uuid::Uuid::new_v4().to_string());
+
+    // Only a stub that builds is saved, a rejected one would fail again on every reload
+    let mut mock = match Mock::try_from_mapping(mapping.clone()) {
+        Ok(mock) => mock,
+        Err(e) => {
+            return json_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                json!({ "errors": [MappingError::from(e)] }),
+            )
+        }
+    };
+
+    if mapping.persistent == Some(true) {
+        let dir = context.config.root_dir.join(MAPPINGS_DIR);
+        match write_mapping_file(&dir, &mapping) {
+            Ok(path) => mock.file_source = Some(FileSource::own_file(path, &mapping)),
+            Err(e) => {
+                return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e }))
+            }
+        }
+    }
+
+    context.registry.register_mock(mock);
+    json_response(StatusCode::CREATED, json!(mapping))
+}
+
+pub(super) fn list_mappings(context: &ServerContext) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::OK,
+        json!({ "mappings": context.registry.mappings() }),
+    )
+}
+
+pub(super) fn get_mapping(context: &ServerContext, id: &str) -> Response<Full<Bytes>> {
+    match context.registry.find_mapping(id) {
+        Some(mapping) => json_response(StatusCode::OK, json!(mapping)),
+        None => no_mapping(id),
+    }
+}
+
+pub(super) fn remove_mapping(context: &ServerContext, id: &str) -> Response<Full<Bytes>> {
+    let removed = match context
+        .registry
+        .removable(|mapping| mapping.id.as_deref() == Some(id))
+    {
+        Ok(removed) => removed,
+        Err(locations) => return shared_mapping_files(&locations),
+    };
+    if removed.is_empty() {
+        return no_mapping(id);
+    }
+    if let Err(e) = delete_mapping_files(&removed) {
+        return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e }));
+    }
+    context.registry.remove_mocks(&removed);
+    json_response(StatusCode::OK, json!({}))
+}
+
+pub(super) fn find_by_metadata(
+    context: &ServerContext,
+    request: &Request,
+) -> Response<Full<Bytes>> {
+    let matcher = match metadata_matcher(request) {
+        Ok(matcher) => matcher,
+        Err((status, error)) => return json_response(status, error),
+    };
+    let mappings = context
+        .registry
+        .mappings()
+        .into_iter()
+        .filter(|mapping| metadata_matches(&matcher, mapping))
+        .collect::<Vec<_>>();
+    json_response(StatusCode::OK, json!({ "mappings": mappings }))
+}
+
+pub(super) fn remove_by_metadata(
+    context: &ServerContext,
+    request: &Request,
+) -> Response<Full<Bytes>> {
+    let matcher = match metadata_matcher(request) {
+        Ok(matcher) => matcher,
+        Err((status, error)) => return json_response(status, error),
+    };
+    let removed = match context
+        .registry
+        .removable(|mapping| metadata_matches(&matcher, mapping))
+    {
+        Ok(removed) => removed,
+        Err(locations) => return shared_mapping_files(&locations),
+    };
+    if let Err(e) = delete_mapping_files(&removed) {
+        return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e }));
+    }
+    context.registry.remove_mocks(&removed);
+    let mappings = removed
+        .iter()
+        .filter_map(|mock| mock.mapping.clone())
+        .collect::<Vec<_>>();
+    json_response(StatusCode::OK, json!({ "mappings": mappings }))
+}
+
+pub(super) fn save_mappings(context: &ServerContext) -> Response<Full<Bytes>> {
+    let dir = context.config.root_dir.join(MAPPINGS_DIR);
+    let mut saved = Vec::new();
+    for mapping in context.registry.runtime_mappings() {
+        match write_mapping_file(&dir, &mapping) {
+            Ok(path) => saved.push(path.display().to_string()),
+            Err(e) => {
+                return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": e }))
+            }
+        }
+    }
+    log::info!("Saved {} mappings to {}", saved.len(), dir.display());
+    // Reloading swaps the saved stubs for ones backed by their new file
+    load_file_mappings(&dir, &context.registry);
+    json_response(StatusCode::OK, json!({ "saved": saved }))
+}
+
+fn metadata_matcher(request: &Request) -> Result<BodyJsonMatcher, (StatusCode, Value)> {
+    let pattern = serde_json::from_slice::<BodyPatternStub>(&request.body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, json!({ "error": e.to_string() })))?;
+    BodyJsonMatcher::try_from(&pattern).map_err(|e| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            json!({ "errors": [MappingError::from(e)] }),
+        )
+    })
+}
+
+fn metadata_matches(matcher: &BodyJsonMatcher, mapping: &StubMappingStub) -> bool {
+    mapping
+        .metadata
+        .as_ref()
+        .is_some_and(|metadata| matcher.matches_json(&Value::Object(metadata.clone())))
+}
+
+fn delete_mapping_files(mocks: &[Arc<Mock>]) -> Result<(), String> {
+    mocks
+        .iter()
+        .filter_map(|mock| mock.file_source.as_ref())
+        .try_for_each(|source| delete_file(&source.path))
+}
+
+fn delete_file(path: &Path) -> Result<(), String> {
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            log::info!("Deleted {}", path.display());
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Cannot delete {} : {}", path.display(), e)),
+    }
+}
+
+fn shared_mapping_files(locations: &[String]) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::CONFLICT,
+        json!({
+            "error": "Stubs stored in a file with other mappings cannot be removed, edit the file instead",
+            "locations": locations,
+        }),
+    )
+}
+
+fn no_mapping(id: &str) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        json!({ "error": format!("No stub mapping with id {}", id) }),
+    )
+}
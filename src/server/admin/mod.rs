@@ -0,0 +1,145 @@
+use crate::net::request::Request;
+use crate::server::recording::RecordSpec;
+use crate::server::ServerContext;
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::{Method, StatusCode};
+use http_body_util::Full;
+use hyper::Response;
+use serde_json::{json, Value};
+
+mod mappings;
+
+pub(crate) const ADMIN_PREFIX: &str = "/__admin";
+
+pub(crate) fn is_admin_request(request: &Request) -> bool {
+    request
+        .url
+        .path()
+        .starts_with(&format!("{}/", ADMIN_PREFIX))
+}
+
+pub(crate) async fn handle_admin_request(
+    context: &ServerContext,
+    request: &Request,
+) -> Response<Full<Bytes>> {
+    let path = &request.url.path()[ADMIN_PREFIX.len()..];
+
+    if let Some(id) = path
+        .strip_prefix("/mappings/")
+        .filter(|id| !["save", "find-by-metadata", "remove-by-metadata"].contains(id))
+    {
+        return match request.method {
+            Method::GET => mappings::get_mapping(context, id),
+            Method::DELETE => mappings::remove_mapping(context, id),
+            _ => not_found(request),
+        };
+    }
+
+    match (&request.method, path) {
+        (&Method::GET, "/mappings") => mappings::list_mappings(context),
+        (&Method::POST, "/mappings") => mappings::create_mapping(context, request),
+        (&Method::POST, "/mappings/save") => mappings::save_mappings(context),
+        (&Method::POST, "/mappings/find-by-metadata") => {
+            mappings::find_by_metadata(context, request)
+        }
+        (&Method::POST, "/mappings/remove-by-metadata") => {
+            mappings::remove_by_metadata(context, request)
+        }
+        (&Method::POST, "/recordings/start") => start_recording(context, request),
+        (&Method::POST, "/recordings/stop") => stop_recording(context),
+        (&Method::GET, "/recordings/status") => recording_status(context),
+        (&Method::GET, "/requests") => received_requests(context),
+        (&Method::GET, "/certs/ca") => ca_certificate(context),
+        (&Method::DELETE, "/requests") => reset_requests(context),
+        _ => not_found(request),
+    }
+}
+
+fn not_found(request: &Request) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        json!({ "error": format!("No admin resource {} {}", request.method, request.url.path()) }),
+    )
+}
+
+fn start_recording(context: &ServerContext, request: &Request) -> Response<Full<Bytes>> {
+    let spec = if request.body.is_empty() {
+        RecordSpec::default()
+    } else {
+        match serde_json::from_slice::<RecordSpec>(&request.body) {
+            Ok(spec) => spec,
+            Err(e) => {
+                return json_response(StatusCode::BAD_REQUEST, json!({ "error": e.to_string() }))
+            }
+        }
+    };
+
+    match context.recorder.start(spec) {
+        Ok(()) => json_response(StatusCode::OK, json!({ "status": "Recording" })),
+        Err(e) => json_response(StatusCode::CONFLICT, json!({ "error": e })),
+    }
+}
+
+fn stop_recording(context: &ServerContext) -> Response<Full<Bytes>> {
+    match context
+        .recorder
+        .stop(&context.config.root_dir, &context.registry)
+    {
+        Ok(mappings) => json_response(StatusCode::OK, json!({ "mappings": mappings })),
+        Err(e) => json_response(StatusCode::CONFLICT, json!({ "error": e })),
+    }
+}
+
+fn recording_status(context: &ServerContext) -> Response<Full<Bytes>> {
+    let status = if context.recorder.is_recording() {
+        "Recording"
+    } else {
+        "Stopped"
+    };
+    json_response(StatusCode::OK, json!({ "status": status }))
+}
+
+fn received_requests(context: &ServerContext) -> Response<Full<Bytes>> {
+    json_response(
+        StatusCode::OK,
+        json!({
+            "requests": context.journal.requests(),
+            "requestJournalDisabled": !context.journal.is_enabled(),
+        }),
+    )
+}
+
+fn reset_requests(context: &ServerContext) -> Response<Full<Bytes>> {
+    context.journal.reset();
+    json_response(StatusCode::OK, json!({}))
+}
+
+fn ca_certificate(context: &ServerContext) -> Response<Full<Bytes>> {
+    match context.ca_certificate.as_ref() {
+        Some(certificate) => {
+            let mut response = Response::new(Full::new(Bytes::from(certificate.clone())));
+            response.headers_mut().insert(
+                CONTENT_TYPE,
+                "application/x-pem-file"
+                    .parse()
+                    .expect("Valid header value"),
+            );
+            response
+        }
+        None => json_response(
+            StatusCode::NOT_FOUND,
+            json!({ "error": "No CA certificate, HTTPS is disabled or uses a supplied certificate" }),
+        ),
+    }
+}
+
+pub(crate) fn json_response(status: StatusCode, body: Value) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        "application/json".parse().expect("Valid header value"),
+    );
+    response
+}
@@ -0,0 +1,181 @@
+use crate::core::journal::JournalSettings;
+use crate::net::request::RequestBodySettings;
+use crate::response::compression::ResponseCompression;
+use crate::response::proxy::ProxyConfig;
+use crate::response::response_template::ResponseSettings;
+use crate::response::templating::{TemplateEngine, TemplateOptions};
+use crate::server::listener::Endpoint;
+use crate::server::protocol::ProtocolSettings;
+use crate::server::recording::RecordSpec;
+use crate::server::tls::{ClientAuth, TlsCertificate};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const MAPPINGS_DIR: &str = "mappings";
+pub const FILES_DIR: &str = "__files";
+
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub port: u16,
+    pub bind_address: IpAddr,
+    pub http_enabled: bool,
+    pub https_port: Option<u16>,
+    pub listeners: Vec<Endpoint>,
+    pub tls_certificate: TlsCertificate,
+    pub client_auth: Option<ClientAuth>,
+    pub protocol: ProtocolSettings,
+    pub response: ResponseSettings,
+    pub request_body: RequestBodySettings,
+    pub trust_forwarded_headers: bool,
+    pub global_response_delay: Option<Duration>,
+    pub journal: JournalSettings,
+    pub fallback_proxy: Option<ProxyConfig>,
+    pub root_dir: PathBuf,
+    pub record_on_startup: Option<RecordSpec>,
+    pub watch_files: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 0,
+            bind_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            http_enabled: true,
+            https_port: None,
+            listeners: Vec::new(),
+            tls_certificate: TlsCertificate::default(),
+            client_auth: None,
+            protocol: ProtocolSettings::default(),
+            response: ResponseSettings::default(),
+            request_body: RequestBodySettings::default(),
+            trust_forwarded_headers: false,
+            global_response_delay: None,
+            journal: JournalSettings::default(),
+            fallback_proxy: None,
+            root_dir: PathBuf::new(),
+            record_on_startup: None,
+            watch_files: false,
+        }
+        .with_root_dir(".")
+    }
+}
+
+impl ServerConfig {
+    pub fn new(port: u16, expose: bool) -> Self {
+        let bind_address = if expose {
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        } else {
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        };
+
+        Self {
+            port,
+            bind_address,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_bind_address(mut self, bind_address: IpAddr) -> Self {
+        self.bind_address = bind_address;
+        self
+    }
+
+    pub fn with_https_port(mut self, https_port: u16) -> Self {
+        self.https_port = Some(https_port);
+        self
+    }
+
+    pub fn with_listener(mut self, endpoint: Endpoint) -> Self {
+        self.listeners.push(endpoint);
+        self
+    }
+
+    pub fn with_tls_certificate(mut self, certificate: TlsCertificate) -> Self {
+        self.tls_certificate = certificate;
+        self
+    }
+
+    pub fn with_client_auth(mut self, client_auth: ClientAuth) -> Self {
+        self.client_auth = Some(client_auth);
+        self
+    }
+
+    pub fn without_http(mut self) -> Self {
+        self.http_enabled = false;
+        self
+    }
+
+    pub fn with_protocol(mut self, protocol: ProtocolSettings) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    pub fn with_global_response_delay(mut self, delay: Duration) -> Self {
+        self.global_response_delay = Some(delay);
+        self
+    }
+
+    pub fn with_request_body(mut self, request_body: RequestBodySettings) -> Self {
+        self.request_body = request_body;
+        self
+    }
+
+    pub fn with_forwarded_headers(mut self) -> Self {
+        self.trust_forwarded_headers = true;
+        self
+    }
+
+    pub fn with_journal(mut self, journal: JournalSettings) -> Self {
+        self.journal = journal;
+        self
+    }
+
+    pub fn with_response_compression(mut self, compression: ResponseCompression) -> Self {
+        self.response.compression = compression;
+        self
+    }
+
+    pub fn with_fallback_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.fallback_proxy = Some(proxy);
+        self
+    }
+
+    pub fn with_template_options(mut self, options: TemplateOptions) -> Self {
+        self.response.templates = Arc::new(TemplateEngine::with_options(options));
+        self
+    }
+
+    pub fn with_root_dir<P: Into<PathBuf>>(mut self, root_dir: P) -> Self {
+        self.root_dir = root_dir.into();
+        self.response.files_root = self.root_dir.join(FILES_DIR);
+        self
+    }
+
+    pub fn with_file_watching(mut self) -> Self {
+        self.watch_files = true;
+        self
+    }
+
+    pub fn with_recording(mut self, spec: RecordSpec) -> Self {
+        self.record_on_startup = Some(spec);
+        self
+    }
+
+    pub(crate) fn endpoints(&self) -> Vec<Endpoint> {
+        let mut endpoints = Vec::new();
+        if self.http_enabled {
+            endpoints.push(Endpoint::tcp(SocketAddr::from((
+                self.bind_address,
+                self.port,
+            ))));
+        }
+        if let Some(https_port) = self.https_port {
+            endpoints
+                .push(Endpoint::tcp(SocketAddr::from((self.bind_address, https_port))).with_tls());
+        }
+        endpoints.extend(self.listeners.iter().cloned());
+        endpoints
+    }
+}
@@ -0,0 +1,285 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+#[cfg(unix)]
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Endpoint {
+    pub address: EndpointAddress,
+    pub tls: bool,
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EndpointAddress {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl Endpoint {
+    pub fn tcp(address: SocketAddr) -> Self {
+        Self {
+            address: EndpointAddress::Tcp(address),
+            tls: false,
+            tag: None,
+        }
+    }
+
+    pub fn unix<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            address: EndpointAddress::Unix(path.into()),
+            tls: false,
+            tag: None,
+        }
+    }
+
+    pub fn with_tls(mut self) -> Self {
+        self.tls = true;
+        self
+    }
+
+    pub fn with_tag<T: Into<String>>(mut self, tag: T) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, tag) = match s.rsplit_once('#') {
+            Some((address, tag)) if !tag.is_empty() => (address, Some(tag)),
+            _ => (s, None),
+        };
+
+        let endpoint = if let Some(path) = address.strip_prefix("unix:") {
+            if path.is_empty() {
+                return Err("expected unix:<path>".to_string());
+            }
+            Endpoint::unix(path)
+        } else if let Some(address) = address.strip_prefix("http://") {
+            Endpoint::tcp(parse_socket_address(address)?)
+        } else if let Some(address) = address.strip_prefix("https://") {
+            Endpoint::tcp(parse_socket_address(address)?).with_tls()
+        } else {
+            return Err(format!(
+                "expected http://<host>:<port>, https://<host>:<port> or unix:<path>, found {}",
+                address
+            ));
+        };
+
+        Ok(match tag {
+            Some(tag) => endpoint.with_tag(tag),
+            None => endpoint,
+        })
+    }
+}
+
+fn parse_socket_address(address: &str) -> Result<SocketAddr, String> {
+    let address = address.trim_end_matches('/');
+    if let Ok(address) = address.parse() {
+        return Ok(address);
+    }
+    address
+        .to_socket_addrs()
+        .map_err(|e| format!("invalid address {} : {}", address, e))?
+        .next()
+        .ok_or_else(|| format!("invalid address {} : host has no address", address))
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.address, self.tls) {
+            (EndpointAddress::Tcp(address), false) => write!(f, "http://{}", address)?,
+            (EndpointAddress::Tcp(address), true) => write!(f, "https://{}", address)?,
+            (EndpointAddress::Unix(path), false) => write!(f, "unix:{}", path.display())?,
+            (EndpointAddress::Unix(path), true) => write!(f, "unix:{} (TLS)", path.display())?,
+        }
+        match &self.tag {
+            Some(tag) => write!(f, "#{}", tag),
+            None => Ok(()),
+        }
+    }
+}
+
+pub(crate) enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+pub(crate) enum Accepted {
+    Tcp(TcpStream, SocketAddr),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Listener {
+    pub(crate) async fn bind(endpoint: &Endpoint) -> Result<Self, String> {
+        let listener = match &endpoint.address {
+            EndpointAddress::Tcp(address) => TcpListener::bind(address).await.map(Listener::Tcp),
+            #[cfg(unix)]
+            EndpointAddress::Unix(path) => {
+                remove_stale_socket(path);
+                UnixListener::bind(path).map(|it| Listener::Unix(it, path.clone()))
+            }
+            #[cfg(not(unix))]
+            EndpointAddress::Unix(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Unix domain sockets are not supported on this platform",
+            )),
+        }
+        .map_err(|e| format!("Failed to bind to {} : {}", endpoint, e))?;
+        log::info!("Listening on {}", endpoint);
+        Ok(listener)
+    }
+
+    pub(crate) async fn accept(&self) -> io::Result<Accepted> {
+        match self {
+            Listener::Tcp(listener) => listener
+                .accept()
+                .await
+                .map(|(stream, address)| Accepted::Tcp(stream, address)),
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => listener
+                .accept()
+                .await
+                .map(|(stream, _)| Accepted::Unix(stream)),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Listener::Unix(_, path) = self {
+            remove_socket(path);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) {
+    if !is_socket(path) {
+        return;
+    }
+    match std::os::unix::net::UnixStream::connect(path) {
+        Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => remove_socket(path),
+        _ => {}
+    }
+}
+
+#[cfg(unix)]
+fn remove_socket(path: &Path) {
+    if is_socket(path) {
+        if let Err(e) = std::fs::remove_file(path) {
+            log::debug!("Cannot remove socket {} : {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_socket(path: &Path) -> bool {
+    std::fs::symlink_metadata(path)
+        .map(|it| it.file_type().is_socket())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tcp_endpoints() {
+        assert_eq!(
+            "http://127.0.0.1:8080".parse::<Endpoint>().unwrap(),
+            Endpoint::tcp("127.0.0.1:8080".parse().unwrap())
+        );
+        assert_eq!(
+            "https://[::1]:8443/#secure".parse::<Endpoint>().unwrap(),
+            Endpoint::tcp("[::1]:8443".parse().unwrap())
+                .with_tls()
+                .with_tag("secure")
+        );
+    }
+
+    #[test]
+    fn resolves_host_names() {
+        let endpoint = "http://localhost:9000".parse::<Endpoint>().unwrap();
+        match endpoint.address {
+            EndpointAddress::Tcp(address) => {
+                assert!(address.ip().is_loopback());
+                assert_eq!(address.port(), 9000);
+            }
+            EndpointAddress::Unix(_) => panic!("expected a TCP endpoint"),
+        }
+        assert!("http://localhost".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn parses_unix_endpoints() {
+        assert_eq!(
+            "unix:/run/spoof.sock#internal".parse::<Endpoint>().unwrap(),
+            Endpoint::unix("/run/spoof.sock").with_tag("internal")
+        );
+        assert!("unix:".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_schemes() {
+        assert!("ftp://127.0.0.1:21".parse::<Endpoint>().is_err());
+        assert!("127.0.0.1:8080".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn displays_as_parsed() {
+        for endpoint in [
+            "http://127.0.0.1:8080",
+            "https://[::1]:8443#a",
+            "unix:/tmp/s#b",
+        ] {
+            assert_eq!(endpoint.parse::<Endpoint>().unwrap().to_string(), endpoint);
+        }
+    }
+
+    #[cfg(unix)]
+    fn socket_path() -> PathBuf {
+        std::env::temp_dir().join(format!("spoof-{}.sock", uuid::Uuid::new_v4()))
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn replaces_a_stale_socket() {
+        let path = socket_path();
+        let stale = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        // Closing the listener leaves the file behind, as a crash would
+        drop(stale);
+        assert!(is_socket(&path));
+
+        let listener = Listener::bind(&Endpoint::unix(&path)).await.unwrap();
+        drop(listener);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn keeps_a_socket_in_use() {
+        let path = socket_path();
+        let live = std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+        assert!(Listener::bind(&Endpoint::unix(&path)).await.is_err());
+        assert!(is_socket(&path));
+
+        drop(live);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
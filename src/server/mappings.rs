@@ -0,0 +1,161 @@
+use crate::core::mock::{FileSource, Mock};
+use crate::core::registry::StubRegistry;
+use crate::model::mapping::files::load_mappings;
+use crate::model::mapping::validation::MappingError;
+use crate::server::config::{FILES_DIR, MAPPINGS_DIR};
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(crate) struct LoadSummary {
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+    pub(crate) changed: usize,
+    pub(crate) unchanged: usize,
+    pub(crate) failed: usize,
+}
+
+impl Display for LoadSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} added, {} removed, {} changed, {} unchanged, {} failed",
+            self.added, self.removed, self.changed, self.unchanged, self.failed
+        )
+    }
+}
+
+pub(crate) fn load_file_mappings(dir: &Path, registry: &StubRegistry) -> Option<LoadSummary> {
+    let loaded = if dir.is_dir() {
+        match load_mappings(dir) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                log::error!("Cannot read mappings from {} : {}", dir.display(), e);
+                return None;
+            }
+        }
+    } else {
+        log::debug!("No mappings directory at {}", dir.display());
+        Vec::new()
+    };
+
+    let mut summary = LoadSummary::default();
+    let mut mocks = Vec::new();
+    for loaded in loaded {
+        let mock = loaded
+            .mapping
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|mapping| {
+                Mock::try_from_mapping(mapping.clone()).map_err(|e| vec![MappingError::from(e)])
+            });
+        match mock {
+            Ok(mut mock) => {
+                mock.file_source = Some(FileSource {
+                    location: loaded.location(),
+                    path: loaded.path.clone(),
+                    shared: !loaded.pointer.is_empty(),
+                    content: serde_json::to_string(&mock.mapping).unwrap_or_default(),
+                });
+                mocks.push(mock);
+            }
+            Err(errors) => {
+                summary.failed += 1;
+                for error in errors {
+                    log::error!("Ignoring mapping {} : {}", loaded.location(), error);
+                }
+            }
+        }
+    }
+
+    let loaded = mocks
+        .iter()
+        .filter_map(|mock| mock.file_source.as_ref())
+        .map(|source| (source.location.clone(), source.content.clone()))
+        .collect::<HashMap<_, _>>();
+    let replaced = registry
+        .replace_file_stubs(mocks)
+        .into_iter()
+        .map(|source| (source.location, source.content))
+        .collect::<HashMap<_, _>>();
+
+    for (location, content) in loaded.iter() {
+        match replaced.get(location) {
+            None => summary.added += 1,
+            Some(previous) if previous != content => summary.changed += 1,
+            Some(_) => summary.unchanged += 1,
+        }
+    }
+    summary.removed = replaced
+        .keys()
+        .filter(|location| !loaded.contains_key(*location))
+        .count();
+    Some(summary)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn reload_on_hangup(root_dir: PathBuf, registry: Arc<StubRegistry>) {
+    let mut hangup_stream = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        .expect("Cannot install SIGHUP signal handler");
+
+    tokio::spawn(async move {
+        while hangup_stream.recv().await.is_some() {
+            log::info!("Received SIGHUP, reloading mappings");
+            reload(&root_dir, &registry);
+        }
+    });
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn reload_on_hangup(_root_dir: PathBuf, _registry: Arc<StubRegistry>) {}
+
+pub(crate) fn reload_on_change(
+    root_dir: PathBuf,
+    registry: Arc<StubRegistry>,
+) -> Result<(), String> {
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if !event.kind.is_access() => {
+                let _ = events_tx.send(());
+            }
+            Ok(_) => {}
+            Err(e) => log::error!("File watcher error : {}", e),
+        })
+        .map_err(|e| format!("Cannot watch mapping files : {}", e))?;
+
+    for dir in [MAPPINGS_DIR, FILES_DIR] {
+        let dir = root_dir.join(dir);
+        if dir.is_dir() {
+            watcher
+                .watch(&dir, RecursiveMode::Recursive)
+                .map_err(|e| format!("Cannot watch {} : {}", dir.display(), e))?;
+            log::info!("Watching {} for changes", dir.display());
+        }
+    }
+
+    tokio::spawn(async move {
+        // Dropping the watcher would stop the notifications
+        let _watcher = watcher;
+        while events_rx.recv().await.is_some() {
+            tokio::time::sleep(WATCH_DEBOUNCE).await;
+            while events_rx.try_recv().is_ok() {}
+            log::info!("Mapping files changed, reloading mappings");
+            reload(&root_dir, &registry);
+        }
+    });
+    Ok(())
+}
+
+fn reload(root_dir: &Path, registry: &StubRegistry) {
+    let dir = root_dir.join(MAPPINGS_DIR);
+    if let Some(summary) = load_file_mappings(&dir, registry) {
+        log::info!("Reloaded mappings from {} : {}", dir.display(), summary);
+    }
+}
@@ -1,36 +1,92 @@
-use bytes::Bytes;
-
-use http_body_util::Full;
+use crate::core::journal::RequestJournal;
+use crate::core::registry::StubRegistry;
+use crate::net::certificate::ClientCertificate;
+use crate::net::connection::{ConnectionInfo, ResettableStream};
+use crate::net::request::{BodyStatus, Request};
+use crate::response::body::{boxed, full_body, ResponseBody};
+use crate::response::fault::Fault;
+use crate::response::files::serve_static;
+use crate::response::proxy::{proxy_client, ProxyClient, ProxyConfig};
+use crate::server::admin::{handle_admin_request, is_admin_request};
+use crate::server::config::ServerConfig;
+use crate::server::listener::{Accepted, Listener};
+use crate::server::protocol::{Activity, HttpProtocols, InFlight};
+use crate::server::recording::Recorder;
+use http::StatusCode;
+use http_body_util::BodyExt;
 use hyper::body::Incoming;
 use hyper::service::service_fn;
-use hyper::{Error, Request, Response};
-use hyper_util::rt::{TokioExecutor, TokioIo};
-use hyper_util::server::conn::auto::Builder;
+use hyper::Response;
+use hyper_util::rt::TokioIo;
+use std::fmt::Display;
 use std::future::Future;
 use std::io;
-use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
 use futures_util::{pin_mut, FutureExt};
-use tokio::net::{TcpListener, TcpStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::watch;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+mod admin;
+pub mod config;
+pub mod listener;
+pub(crate) mod mappings;
+pub mod protocol;
+pub mod recording;
+pub mod tls;
+
+struct ServerContext {
+    config: ServerConfig,
+    registry: Arc<StubRegistry>,
+    proxy_client: ProxyClient,
+    recorder: Recorder,
+    journal: RequestJournal,
+    ca_certificate: Option<String>,
+}
 
-pub(crate) async fn start_server<F>(port: u16, expose: bool, shutdown: F) -> Result<(), String>
+pub(crate) async fn start_server<F>(
+    config: ServerConfig,
+    registry: Arc<StubRegistry>,
+    shutdown: F,
+) -> Result<(), String>
 where
     F: Future<Output = ()> + Send + 'static,
 {
-    let host: [u8; 4] = if expose { [0, 0, 0, 0] } else { [127, 0, 0, 1] };
-
-    let addr: SocketAddr = SocketAddr::from((host, port));
+    let endpoints = config.endpoints();
+    if endpoints.is_empty() {
+        return Err("HTTP is disabled and no HTTPS port or listener is set".to_string());
+    }
+    let mut listeners = Vec::new();
+    for endpoint in endpoints {
+        listeners.push((Listener::bind(&endpoint).await?, endpoint));
+    }
 
-    let listener = TcpListener::bind(addr)
-        .await
-        .expect(&*format!("Failed to bind to {}", addr.to_string()));
+    let tls = match listeners.iter().any(|(_, endpoint)| endpoint.tls) {
+        true => Some(
+            config
+                .tls_certificate
+                .load(config.client_auth.as_ref(), config.protocol.protocols)?,
+        ),
+        false => None,
+    };
 
-    log::info!("Listening on {}", addr.to_string());
+    let context = Arc::new(ServerContext {
+        journal: RequestJournal::new(config.journal.clone()),
+        config,
+        registry,
+        proxy_client: proxy_client(),
+        recorder: Recorder::default(),
+        ca_certificate: tls.as_ref().and_then(|tls| tls.ca_certificate.clone()),
+    });
+    let tls_acceptor = tls.map(|tls| tls.acceptor);
 
-    let service =
-        service_fn(move |req| async move { Ok::<_, Error>(handle_server_request(req).await) });
+    if let Some(spec) = context.config.record_on_startup.clone() {
+        context.recorder.start(spec)?;
+    }
 
     // Channel to send shutdown signal
     let (signal_tx, signal_rx) = tokio::sync::watch::channel(());
@@ -46,11 +102,43 @@ where
     // Channel to know if all connections are closed/gracefully shutdown
     let (close_tx, close_rx) = tokio::sync::watch::channel(());
 
+    for (listener, endpoint) in listeners {
+        tokio::spawn(accept_connections(
+            listener,
+            endpoint.tag.as_deref().map(Arc::from),
+            tls_acceptor.clone().filter(|_| endpoint.tls),
+            Arc::clone(&context),
+            Arc::clone(&signal_tx),
+            close_rx.clone(),
+        ));
+    }
+
+    drop(close_rx);
+
+    close_tx.closed().await;
+
+    if context.recorder.is_recording() {
+        context
+            .recorder
+            .stop(&context.config.root_dir, &context.registry)?;
+    }
+
+    Ok(())
+}
+
+async fn accept_connections(
+    listener: Listener,
+    tag: Option<Arc<str>>,
+    tls_acceptor: Option<TlsAcceptor>,
+    context: Arc<ServerContext>,
+    signal_tx: Arc<watch::Sender<()>>,
+    close_rx: watch::Receiver<()>,
+) {
     loop {
-        let (tcp_stream, socket_addr) = tokio::select! {
-            conn = tcp_accept(&listener) => {
+        let accepted = tokio::select! {
+            conn = accept(&listener) => {
                 match conn {
-                    Some(conn) => conn,
+                    Some(accepted) => accepted,
                     None => continue,
                 }
             }
@@ -61,51 +149,171 @@ where
             }
         };
 
-        let tcp_stream = TokioIo::new(tcp_stream);
-
-        log::trace!("connection to tcp socket : {socket_addr} is established");
-
+        let connection = ConnectionInfo {
+            listener: tag.clone(),
+            trust_forwarded_headers: context.config.trust_forwarded_headers,
+            ..Default::default()
+        };
+        let context = Arc::clone(&context);
         let signal_tx = Arc::clone(&signal_tx);
         let close_rx = close_rx.clone();
+        let tls_acceptor = tls_acceptor.clone();
 
-        tokio::spawn(async move {
-            let builder = Builder::new(TokioExecutor::new());
-            let conn = builder.serve_connection_with_upgrades(tcp_stream, service);
+        match accepted {
+            Accepted::Tcp(tcp_stream, socket_addr) => {
+                log::trace!("connection to tcp socket : {socket_addr} is established");
+                let connection = ConnectionInfo {
+                    remote_address: Some(socket_addr),
+                    ..connection
+                };
+                let tcp_stream = ResettableStream::new(tcp_stream, &connection);
+                tokio::spawn(async move {
+                    accept_connection(tcp_stream, tls_acceptor, context, connection, signal_tx)
+                        .await;
+                    drop(close_rx);
+                });
+            }
+            #[cfg(unix)]
+            Accepted::Unix(unix_stream) => {
+                log::trace!("connection to unix socket is established");
+                tokio::spawn(async move {
+                    accept_connection(unix_stream, tls_acceptor, context, connection, signal_tx)
+                        .await;
+                    drop(close_rx);
+                });
+            }
+        }
+    }
+}
 
-            pin_mut!(conn);
+async fn accept_connection<I>(
+    io: I,
+    tls_acceptor: Option<TlsAcceptor>,
+    context: Arc<ServerContext>,
+    connection: ConnectionInfo,
+    signal_tx: Arc<watch::Sender<()>>,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    match tls_acceptor {
+        Some(tls_acceptor) => match tls_acceptor.accept(io).await {
+            Ok(tls_stream) => {
+                let connection = ConnectionInfo {
+                    secure: true,
+                    client_certificate: client_certificate(&tls_stream),
+                    ..connection
+                };
+                serve_connection(tls_stream, context, connection, signal_tx).await
+            }
+            Err(e) => match connection.remote_address {
+                Some(socket_addr) => {
+                    log::debug!("TLS handshake with {} failed : {}", socket_addr, e)
+                }
+                None => log::debug!("TLS handshake failed : {}", e),
+            },
+        },
+        None => serve_connection(io, context, connection, signal_tx).await,
+    }
+}
 
-            let signal_closed = signal_tx.closed().fuse();
+fn client_certificate<I>(tls_stream: &TlsStream<I>) -> Option<Arc<ClientCertificate>> {
+    let der = tls_stream.get_ref().1.peer_certificates()?.first()?;
+    match ClientCertificate::from_der(der) {
+        Ok(certificate) => Some(Arc::new(certificate)),
+        Err(e) => {
+            log::debug!("{}", e);
+            None
+        }
+    }
+}
 
-            pin_mut!(signal_closed);
+async fn serve_connection<I>(
+    io: I,
+    context: Arc<ServerContext>,
+    connection: ConnectionInfo,
+    signal_tx: Arc<watch::Sender<()>>,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let settings = context.config.protocol.clone();
+    let (activity_tx, activity_rx) = watch::channel(Activity::default());
+    let activity_tx = Arc::new(activity_tx);
 
-            loop {
-                tokio::select! {
-                    result = conn.as_mut() => {
-                        if let Err(_e) = result {
-                            log::trace!("Failed to serve connection : {_e:#}");
-                        }
-                        break;
-                    }
-                    _ = &mut signal_closed => {
-                        log::trace!("Signal received, starting graceful shutdown");
-                        conn.as_mut().graceful_shutdown();
-                    }
-                }
-            }
+    let service = service_fn(move |mut req: hyper::Request<Incoming>| {
+        let context = Arc::clone(&context);
+        let in_flight = InFlight::start(&activity_tx);
+        req.extensions_mut().insert(connection.clone());
+        async move {
+            let response = handle_server_request(context, req).await;
+            drop(in_flight);
+            response
+        }
+    });
 
-            drop(close_rx);
-        });
+    let io = TokioIo::new(io);
+    let idle = protocol::idle(&settings, activity_rx);
+    match settings.protocols {
+        HttpProtocols::Auto => {
+            let builder = settings.auto_builder();
+            let conn = builder.serve_connection_with_upgrades(io, service);
+            drive(conn, |conn| conn.graceful_shutdown(), signal_tx, idle).await
+        }
+        HttpProtocols::Http1Only => {
+            let conn = settings
+                .http1_builder()
+                .serve_connection(io, service)
+                .with_upgrades();
+            drive(conn, |conn| conn.graceful_shutdown(), signal_tx, idle).await
+        }
+        HttpProtocols::Http2Only => {
+            let conn = settings.http2_builder().serve_connection(io, service);
+            drive(conn, |conn| conn.graceful_shutdown(), signal_tx, idle).await
+        }
     }
+}
 
-    drop(close_rx);
-    drop(listener);
+async fn drive<C, E>(
+    conn: C,
+    graceful_shutdown: impl Fn(Pin<&mut C>),
+    signal_tx: Arc<watch::Sender<()>>,
+    idle: impl Future<Output = bool>,
+) where
+    C: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    pin_mut!(conn);
 
-    close_tx.closed().await;
+    let signal_closed = signal_tx.closed().fuse();
+    let idle = idle.fuse();
 
-    Ok(())
+    pin_mut!(signal_closed);
+    pin_mut!(idle);
+
+    loop {
+        tokio::select! {
+            result = conn.as_mut() => {
+                if let Err(_e) = result {
+                    log::trace!("Failed to serve connection : {_e:#}");
+                }
+                break;
+            }
+            _ = &mut signal_closed => {
+                log::trace!("Signal received, starting graceful shutdown");
+                graceful_shutdown(conn.as_mut());
+            }
+            used = &mut idle => {
+                if !used {
+                    log::trace!("Connection is idle and unused, closing it");
+                    break;
+                }
+                log::trace!("Connection is idle, starting graceful shutdown");
+                graceful_shutdown(conn.as_mut());
+            }
+        }
+    }
 }
 
-async fn tcp_accept(listener: &TcpListener) -> Option<(TcpStream, SocketAddr)> {
+async fn accept(listener: &Listener) -> Option<Accepted> {
     match listener.accept().await {
         Ok(conn) => Some(conn),
         Err(e) => {
@@ -129,6 +337,126 @@ fn is_connection_error(error: &io::Error) -> bool {
     )
 }
 
-async fn handle_server_request(_: Request<Incoming>) -> Response<Full<Bytes>> {
-    Response::new(Full::new(Bytes::from("Hello World!")))
+async fn handle_server_request(
+    context: Arc<ServerContext>,
+    request: hyper::Request<Incoming>,
+) -> Result<Response<ResponseBody>, Fault> {
+    let connection = request.extensions().get::<ConnectionInfo>().cloned();
+    let request = Request::from_hyper(request, &context.config.request_body).await;
+
+    let rejected = match &request.body_status {
+        BodyStatus::Failed(e) => {
+            log::warn!(
+                "Cannot read the body of {} {} : {}",
+                request.method,
+                request.url,
+                e
+            );
+            Some((
+                StatusCode::BAD_REQUEST,
+                "Cannot read the request body".to_string(),
+            ))
+        }
+        BodyStatus::TooLarge { max_size } => {
+            log::warn!(
+                "Rejecting {} {}, its body is larger than {} bytes",
+                request.method,
+                request.url,
+                max_size
+            );
+            Some((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Request body is larger than {} bytes", max_size),
+            ))
+        }
+        BodyStatus::Complete | BodyStatus::Digested { .. } => None,
+    };
+    if let Some((status, message)) = rejected {
+        context.journal.record(&request, false);
+        let mut response = Response::new(full_body(message));
+        *response.status_mut() = status;
+        return Ok(response);
+    }
+
+    if is_admin_request(&request) {
+        return Ok(handle_admin_request(&context, &request).await.map(boxed));
+    }
+
+    let mock = context.registry.find_match(&request);
+    context.journal.record(&request, mock.is_some());
+
+    match mock {
+        Some(mock) => {
+            let response = mock.next_response(context.config.response.templates.rng());
+            let delay = [response.delay(), context.config.global_response_delay]
+                .into_iter()
+                .flatten()
+                .sum::<Duration>();
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            if let Some(fault) = response.fault() {
+                log::debug!(
+                    "Answering {} {} with {}",
+                    request.method,
+                    request.url,
+                    fault
+                );
+                if let (Fault::ConnectionResetByPeer, Some(connection)) = (fault, &connection) {
+                    connection.request_reset();
+                }
+                return fault.into_response();
+            }
+            if let Some(proxy) = response.proxy() {
+                return Ok(proxy_request(&context, proxy, &request).await);
+            }
+            Ok(response
+                .build_response(
+                    &request,
+                    &mock.path_parameters(&request),
+                    &context.config.response,
+                )
+                .await)
+        }
+        None => {
+            if let Some(response) =
+                serve_static(&context.config.response.files_root, &request).await
+            {
+                return Ok(response);
+            }
+
+            let proxy = context
+                .recorder
+                .target()
+                .or_else(|| context.config.fallback_proxy.clone());
+            if let Some(proxy) = proxy {
+                return Ok(proxy_request(&context, &proxy, &request).await);
+            }
+            log::debug!("No stub matched {} {}", request.method, request.url);
+            let mut response = Response::new(full_body("No stub matched the request"));
+            *response.status_mut() = StatusCode::NOT_FOUND;
+            Ok(response)
+        }
+    }
+}
+
+async fn proxy_request(
+    context: &ServerContext,
+    proxy: &ProxyConfig,
+    request: &Request,
+) -> Response<ResponseBody> {
+    let response = proxy.forward(&context.proxy_client, request).await;
+    if !context.recorder.is_recording() {
+        return response.map(boxed);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match body.collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(never) => match never {},
+    };
+    context
+        .recorder
+        .record(request, parts.status, &parts.headers, &body);
+    Response::from_parts(parts, full_body(body))
 }
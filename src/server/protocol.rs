@@ -0,0 +1,201 @@
+use hyper::server::conn::{http1, http2};
+use hyper_util::rt::{TokioExecutor, TokioTimer};
+use hyper_util::server::conn::auto;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+const MIN_HTTP1_HEADER_SIZE: usize = 8192;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum HttpProtocols {
+    #[default]
+    Auto,
+    Http1Only,
+    Http2Only,
+}
+
+impl HttpProtocols {
+    pub(crate) fn alpn_protocols(&self) -> Vec<Vec<u8>> {
+        match self {
+            HttpProtocols::Auto => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+            HttpProtocols::Http1Only => vec![b"http/1.1".to_vec()],
+            HttpProtocols::Http2Only => vec![b"h2".to_vec()],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProtocolSettings {
+    pub protocols: HttpProtocols,
+    pub header_read_timeout: Option<Duration>,
+    pub keep_alive: bool,
+    pub idle_timeout: Option<Duration>,
+    pub max_concurrent_streams: Option<u32>,
+    pub max_header_size: Option<usize>,
+}
+
+impl Default for ProtocolSettings {
+    fn default() -> Self {
+        Self {
+            protocols: HttpProtocols::default(),
+            header_read_timeout: None,
+            keep_alive: true,
+            idle_timeout: None,
+            max_concurrent_streams: None,
+            max_header_size: None,
+        }
+    }
+}
+
+impl ProtocolSettings {
+    pub(crate) fn auto_builder(&self) -> auto::Builder<TokioExecutor> {
+        let mut builder = auto::Builder::new(TokioExecutor::new());
+        {
+            let mut http1 = builder.http1();
+            http1.timer(TokioTimer::new()).keep_alive(self.keep_alive);
+            if let Some(timeout) = self.header_read_timeout {
+                http1.header_read_timeout(timeout);
+            }
+            if let Some(size) = self.max_header_size {
+                http1.max_buf_size(size.max(MIN_HTTP1_HEADER_SIZE));
+            }
+        }
+        {
+            let mut http2 = builder.http2();
+            http2.timer(TokioTimer::new());
+            // Passing None would lift hyper's default limit altogether
+            if let Some(max) = self.max_concurrent_streams {
+                http2.max_concurrent_streams(max);
+            }
+            if let Some(size) = self.max_header_size {
+                http2.max_header_list_size(u32::try_from(size).unwrap_or(u32::MAX));
+            }
+        }
+        builder
+    }
+
+    pub(crate) fn http1_builder(&self) -> http1::Builder {
+        let mut builder = http1::Builder::new();
+        builder
+            .timer(TokioTimer::new())
+            .keep_alive(self.keep_alive)
+            .header_read_timeout(self.header_read_timeout);
+        if let Some(size) = self.max_header_size {
+            builder.max_buf_size(size.max(MIN_HTTP1_HEADER_SIZE));
+        }
+        builder
+    }
+
+    pub(crate) fn http2_builder(&self) -> http2::Builder<TokioExecutor> {
+        let mut builder = http2::Builder::new(TokioExecutor::new());
+        builder.timer(TokioTimer::new());
+        if let Some(max) = self.max_concurrent_streams {
+            builder.max_concurrent_streams(max);
+        }
+        if let Some(size) = self.max_header_size {
+            builder.max_header_list_size(u32::try_from(size).unwrap_or(u32::MAX));
+        }
+        builder
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Activity {
+    in_flight: usize,
+    served: usize,
+}
+
+pub(crate) struct InFlight(Arc<watch::Sender<Activity>>);
+
+impl InFlight {
+    pub(crate) fn start(activity: &Arc<watch::Sender<Activity>>) -> Self {
+        activity.send_modify(|it| it.in_flight += 1);
+        Self(Arc::clone(activity))
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.send_modify(|it| {
+            it.in_flight -= 1;
+            it.served += 1;
+        });
+    }
+}
+
+pub(crate) async fn idle(
+    settings: &ProtocolSettings,
+    mut activity: watch::Receiver<Activity>,
+) -> bool {
+    if settings.keep_alive && settings.idle_timeout.is_none() {
+        return std::future::pending().await;
+    }
+
+    loop {
+        let current = *activity.borrow_and_update();
+        let used = current.in_flight > 0 || current.served > 0;
+        if current.in_flight == 0 {
+            if !settings.keep_alive && current.served > 0 {
+                return used;
+            }
+            if let Some(idle_timeout) = settings.idle_timeout {
+                tokio::select! {
+                    _ = tokio::time::sleep(idle_timeout) => return used,
+                    changed = activity.changed() => if changed.is_err() { return used },
+                }
+                continue;
+            }
+        }
+        if activity.changed().await.is_err() {
+            return used;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity() -> Arc<watch::Sender<Activity>> {
+        Arc::new(watch::channel(Activity::default()).0)
+    }
+
+    #[tokio::test]
+    async fn closes_after_the_first_response_without_keep_alive() {
+        let settings = ProtocolSettings {
+            keep_alive: false,
+            ..Default::default()
+        };
+        let activity = activity();
+        let idle = tokio::spawn({
+            let receiver = activity.subscribe();
+            async move { idle(&settings, receiver).await }
+        });
+
+        let request = InFlight::start(&activity);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!idle.is_finished());
+
+        drop(request);
+        assert!(idle.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn closes_unused_connections_after_the_idle_timeout() {
+        let settings = ProtocolSettings {
+            idle_timeout: Some(Duration::from_millis(20)),
+            ..Default::default()
+        };
+        assert!(!idle(&settings, activity().subscribe()).await);
+    }
+
+    #[test]
+    fn offers_only_the_configured_protocols() {
+        assert_eq!(HttpProtocols::Http2Only.alpn_protocols(), [b"h2".to_vec()]);
+        assert_eq!(
+            HttpProtocols::Auto.alpn_protocols(),
+            [b"h2".to_vec(), b"http/1.1".to_vec()]
+        );
+    }
+}
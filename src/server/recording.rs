@@ -0,0 +1,435 @@
+use crate::core::mock::{FileSource, Mock};
+use crate::core::registry::StubRegistry;
+use crate::model::mapping::StubMappingStub;
+use crate::model::request::body::BodyPatternStub;
+use crate::model::request::method::MethodStub;
+use crate::model::request::RequestPatternStub;
+use crate::model::response::definition::{headers_to_json, ResponseDefinition};
+use crate::net::request::Request;
+use crate::response::proxy::ProxyConfig;
+use crate::server::config::{FILES_DIR, MAPPINGS_DIR};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use bytes::Bytes;
+use http::header::{CONNECTION, CONTENT_LENGTH, CONTENT_TYPE, DATE, TRANSFER_ENCODING};
+use http::{HeaderMap, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use std::sync::Mutex;
+use url::Url;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RecordSpec {
+    pub target_base_url: Option<String>,
+    pub capture_method: bool,
+    pub capture_query: bool,
+    pub capture_headers: Vec<String>,
+    pub request_body_pattern: RecordedBodyPattern,
+    pub persist: bool,
+}
+
+impl Default for RecordSpec {
+    fn default() -> Self {
+        Self {
+            target_base_url: None,
+            capture_method: true,
+            capture_query: true,
+            capture_headers: Vec::new(),
+            request_body_pattern: RecordedBodyPattern::default(),
+            persist: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RecordedBodyPattern {
+    pub matcher: RecordedBodyMatcher,
+    pub ignore_array_order: bool,
+    pub ignore_extra_elements: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedBodyMatcher {
+    #[default]
+    Auto,
+    EqualTo,
+    EqualToJson,
+    None,
+}
+
+struct RecordedExchange {
+    request: RequestPatternStub,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+struct RecordingSession {
+    spec: RecordSpec,
+    target: Option<ProxyConfig>,
+    exchanges: Vec<RecordedExchange>,
+}
+
+#[derive(Default)]
+pub(crate) struct Recorder {
+    session: Mutex<Option<RecordingSession>>,
+}
+
+impl Recorder {
+    pub(crate) fn start(&self, spec: RecordSpec) -> Result<(), String> {
+        let target = match spec.target_base_url.as_deref() {
+            Some(target) => match Url::parse(target) {
+                Ok(_) => Some(ProxyConfig::new(target)),
+                Err(e) => return Err(format!("Invalid target base URL {} : {}", target, e)),
+            },
+            None => None,
+        };
+
+        let mut session = self.session.lock().expect("Recorder lock poisoned");
+        if session.is_some() {
+            return Err("Recording is already in progress".to_string());
+        }
+
+        log::info!(
+            "Started recording{}",
+            spec.target_base_url
+                .as_deref()
+                .map(|target| format!(" from {}", target))
+                .unwrap_or_default()
+        );
+        *session = Some(RecordingSession {
+            spec,
+            target,
+            exchanges: Vec::new(),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        self.session
+            .lock()
+            .expect("Recorder lock poisoned")
+            .is_some()
+    }
+
+    pub(crate) fn target(&self) -> Option<ProxyConfig> {
+        self.session
+            .lock()
+            .expect("Recorder lock poisoned")
+            .as_ref()
+            .and_then(|session| session.target.clone())
+    }
+
+    pub(crate) fn record(
+        &self,
+        request: &Request,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &Bytes,
+    ) {
+        let mut session = self.session.lock().expect("Recorder lock poisoned");
+        let Some(session) = session.as_mut() else {
+            return;
+        };
+
+        let pattern = request_pattern(&session.spec, request);
+        // Identical requests collapse into the first recorded response
+        if session
+            .exchanges
+            .iter()
+            .any(|exchange| exchange.request == pattern)
+        {
+            return;
+        }
+
+        session.exchanges.push(RecordedExchange {
+            request: pattern,
+            status,
+            headers: recorded_headers(headers),
+            body: body.clone(),
+        });
+    }
+
+    pub(crate) fn stop(
+        &self,
+        root_dir: &Path,
+        registry: &StubRegistry,
+    ) -> Result<Vec<StubMappingStub>, String> {
+        let session = self
+            .session
+            .lock()
+            .expect("Recorder lock poisoned")
+            .take()
+            .ok_or_else(|| "No recording is in progress".to_string())?;
+
+        if session.spec.persist {
+            for dir in [MAPPINGS_DIR, FILES_DIR] {
+                std::fs::create_dir_all(root_dir.join(dir)).map_err(|e| {
+                    format!("Cannot create {} : {}", root_dir.join(dir).display(), e)
+                })?;
+            }
+        }
+
+        let mut mappings = Vec::new();
+        for exchange in session.exchanges {
+            let id = uuid::Uuid::new_v4().to_string();
+            let name = file_name(&exchange.request, &id);
+
+            let mut response = ResponseDefinition {
+                status: Some(exchange.status.as_u16()),
+                headers: Some(headers_to_json(&exchange.headers)),
+                ..Default::default()
+            };
+            if session.spec.persist && !exchange.body.is_empty() {
+                let body_file_name = format!("{}.{}", name, file_extension(&exchange.headers));
+                let body_path = root_dir.join(FILES_DIR).join(&body_file_name);
+                std::fs::write(&body_path, &exchange.body)
+                    .map_err(|e| format!("Cannot write {} : {}", body_path.display(), e))?;
+                response.body_file_name = Some(body_file_name);
+            } else if !exchange.body.is_empty() {
+                match std::str::from_utf8(&exchange.body) {
+                    Ok(body) => response.body = Some(body.to_string()),
+                    Err(_) => response.base64_body = Some(BASE64_STANDARD.encode(&exchange.body)),
+                }
+            }
+
+            let mapping = StubMappingStub {
+                id: Some(id),
+                request: exchange.request,
+                response: Some(response),
+                ..Default::default()
+            };
+            let mut mock = Mock::try_from_mapping(mapping.clone()).map_err(|e| e.to_string())?;
+
+            // A persisted stub is backed by its file, so a reload replaces it instead of adding it again
+            if session.spec.persist {
+                let mapping_path = root_dir.join(MAPPINGS_DIR).join(format!("{}.json", name));
+                let json = serde_json::to_vec_pretty(&mapping).map_err(|e| e.to_string())?;
+                std::fs::write(&mapping_path, json)
+                    .map_err(|e| format!("Cannot write {} : {}", mapping_path.display(), e))?;
+                mock.file_source = Some(FileSource::own_file(mapping_path, &mapping));
+            }
+
+            registry.register_mock(mock);
+            mappings.push(mapping);
+        }
+
+        log::info!(
+            "Stopped recording, captured {} stub mappings",
+            mappings.len()
+        );
+        Ok(mappings)
+    }
+}
+
+fn request_pattern(spec: &RecordSpec, request: &Request) -> RequestPatternStub {
+    let mut pattern = RequestPatternStub::default();
+
+    if spec.capture_method {
+        pattern.method.method = Some(MethodStub::Single(request.method.to_string()));
+    }
+    pattern.path.path_equals = Some(request.url.path().to_string());
+
+    if spec.capture_query && request.url.query().is_some() {
+        let mut values = Vec::<(String, Vec<String>)>::new();
+        for (key, value) in request.url.query_pairs() {
+            match values.iter_mut().find(|(it, _)| *it == key) {
+                Some((_, values)) => values.push(value.into_owned()),
+                None => values.push((key.into_owned(), vec![value.into_owned()])),
+            }
+        }
+        let query = values
+            .into_iter()
+            .map(|(key, values)| {
+                let matcher = match values.as_slice() {
+                    [value] => json!({ "equalTo": value }),
+                    values => json!({
+                        "hasExactly": values
+                            .iter()
+                            .map(|value| json!({ "equalTo": value }))
+                            .collect::<Vec<_>>()
+                    }),
+                };
+                (key, matcher)
+            })
+            .collect::<Map<_, _>>();
+        pattern.query.query_params = Some(query);
+    }
+
+    let headers = spec
+        .capture_headers
+        .iter()
+        .filter_map(|name| {
+            let value = request.headers.get(name.as_str())?.to_str().ok()?;
+            Some((name.clone(), json!({ "equalTo": value })))
+        })
+        .collect::<Map<_, _>>();
+    if !headers.is_empty() {
+        pattern.headers.headers = Some(headers);
+    }
+
+    pattern.body.body_patterns =
+        body_pattern(&spec.request_body_pattern, &request.body).map(|it| vec![it]);
+    pattern
+}
+
+fn body_pattern(spec: &RecordedBodyPattern, body: &[u8]) -> Option<BodyPatternStub> {
+    if body.is_empty() || spec.matcher == RecordedBodyMatcher::None {
+        return None;
+    }
+
+    let json = serde_json::from_slice::<Value>(body).ok();
+    let text = std::str::from_utf8(body).ok();
+
+    match (spec.matcher, json, text) {
+        (RecordedBodyMatcher::Auto | RecordedBodyMatcher::EqualToJson, Some(json), _) => {
+            Some(BodyPatternStub {
+                equal_to_json: Some(json),
+                ignore_array_order: Some(spec.ignore_array_order).filter(|it| *it),
+                ignore_extra_elements: Some(spec.ignore_extra_elements).filter(|it| *it),
+                ..Default::default()
+            })
+        }
+        (_, _, Some(text)) => Some(BodyPatternStub {
+            equal_to: Some(text.to_string()),
+            ..Default::default()
+        }),
+        _ => Some(BodyPatternStub {
+            binary_equal_to: Some(BASE64_STANDARD.encode(body)),
+            ..Default::default()
+        }),
+    }
+}
+
+fn recorded_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    for name in [CONTENT_LENGTH, DATE, TRANSFER_ENCODING, CONNECTION] {
+        headers.remove(name);
+    }
+    headers
+}
+
+fn file_name(request: &RequestPatternStub, id: &str) -> String {
+    let method = match request.method.method.as_ref() {
+        Some(MethodStub::Single(method)) => method.to_lowercase(),
+        _ => "any".to_string(),
+    };
+    let path = request
+        .path
+        .path_equals
+        .as_deref()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    let path = path
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let path = if path.is_empty() {
+        "root".to_string()
+    } else {
+        path.chars().take(64).collect()
+    };
+
+    format!("{}-{}-{}", method, path, &id[..8])
+}
+
+fn file_extension(headers: &HeaderMap) -> &'static str {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    [
+        ("json", "json"),
+        ("html", "html"),
+        ("xml", "xml"),
+        ("javascript", "js"),
+        ("css", "css"),
+        ("text/", "txt"),
+        ("image/png", "png"),
+        ("image/jpeg", "jpg"),
+        ("image/gif", "gif"),
+        ("pdf", "pdf"),
+    ]
+    .iter()
+    .find(|(needle, _)| content_type.contains(needle))
+    .map(|(_, extension)| *extension)
+    .unwrap_or("bin")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::mappings::load_file_mappings;
+
+    fn recorder(persist: bool) -> Recorder {
+        let recorder = Recorder::default();
+        let spec = RecordSpec {
+            persist,
+            ..Default::default()
+        };
+        recorder.start(spec).unwrap();
+        recorder
+    }
+
+    #[test]
+    fn repeated_query_keys_keep_every_value() {
+        let request = Request::get("http://localhost/search?tag=a&q=x&tag=b");
+        let pattern = request_pattern(&RecordSpec::default(), &request);
+        assert_eq!(
+            Value::Object(pattern.query.query_params.unwrap()),
+            json!({
+                "tag": { "hasExactly": [{ "equalTo": "a" }, { "equalTo": "b" }] },
+                "q": { "equalTo": "x" }
+            })
+        );
+    }
+
+    #[test]
+    fn recorded_stubs_keep_their_mapping() {
+        let recorder = recorder(false);
+        let request = Request::get("http://localhost/image");
+        let body = Bytes::from_static(&[0xff, 0xd8, 0xff]);
+        recorder.record(&request, StatusCode::OK, &HeaderMap::new(), &body);
+
+        let registry = StubRegistry::new();
+        let mappings = recorder.stop(&std::env::temp_dir(), &registry).unwrap();
+        let id = mappings[0].id.as_deref().unwrap();
+        let mapping = registry.find_mapping(id).unwrap();
+        let response = mapping.response.unwrap();
+        assert_eq!(response.base64_body.as_deref(), Some("/9j/"));
+        assert_eq!(response.body, None);
+        assert!(registry.find_match(&request).is_some());
+    }
+
+    #[test]
+    fn persisted_stubs_are_replaced_on_reload() {
+        let root_dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let recorder = recorder(true);
+        let request = Request::get("http://localhost/users");
+        let body = Bytes::from_static(b"[]");
+        recorder.record(&request, StatusCode::OK, &HeaderMap::new(), &body);
+
+        let registry = StubRegistry::new();
+        recorder.stop(&root_dir, &registry).unwrap();
+        let summary = load_file_mappings(&root_dir.join(MAPPINGS_DIR), &registry).unwrap();
+        std::fs::remove_dir_all(&root_dir).unwrap();
+
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(registry.mappings().len(), 1);
+    }
+}
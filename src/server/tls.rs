@@ -0,0 +1,155 @@
+use crate::server::protocol::HttpProtocols;
+use rcgen::{
+    BasicConstraints, CertificateParams, CertifiedIssuer, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose,
+};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::RootCertStore;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio_rustls::TlsAcceptor;
+
+#[derive(Clone, Debug)]
+pub enum TlsCertificate {
+    SelfSigned { subject_alt_names: Vec<String> },
+    Pem {
+        certificate: PathBuf,
+        private_key: PathBuf,
+    },
+}
+
+impl Default for TlsCertificate {
+    fn default() -> Self {
+        TlsCertificate::SelfSigned {
+            subject_alt_names: vec![
+                "localhost".to_string(),
+                "127.0.0.1".to_string(),
+                "::1".to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientAuth {
+    pub trusted_certificates: PathBuf,
+    pub required: bool,
+}
+
+pub(crate) struct TlsIdentity {
+    pub(crate) acceptor: TlsAcceptor,
+    pub(crate) ca_certificate: Option<String>,
+}
+
+impl TlsCertificate {
+    pub(crate) fn load(
+        &self,
+        client_auth: Option<&ClientAuth>,
+        protocols: HttpProtocols,
+    ) -> Result<TlsIdentity, String> {
+        let (chain, key, ca_certificate) = match self {
+            TlsCertificate::SelfSigned { subject_alt_names } => {
+                let (chain, key, ca) = self_signed(subject_alt_names)
+                    .map_err(|e| format!("Cannot generate TLS certificate : {}", e))?;
+                (chain, key, Some(ca))
+            }
+            TlsCertificate::Pem {
+                certificate,
+                private_key,
+            } => {
+                let chain = CertificateDer::pem_file_iter(certificate)
+                    .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+                    .map_err(|e| {
+                        format!("Cannot read certificate {} : {}", certificate.display(), e)
+                    })?;
+                let key = PrivateKeyDer::from_pem_file(private_key).map_err(|e| {
+                    format!("Cannot read private key {} : {}", private_key.display(), e)
+                })?;
+                (chain, key, None)
+            }
+        };
+
+        let builder = rustls::ServerConfig::builder();
+        let builder = match client_auth {
+            Some(client_auth) => builder.with_client_cert_verifier(client_auth.verifier()?),
+            None => builder.with_no_client_auth(),
+        };
+        let mut config = builder
+            .with_single_cert(chain, key)
+            .map_err(|e| format!("Invalid TLS certificate : {}", e))?;
+        config.alpn_protocols = protocols.alpn_protocols();
+
+        Ok(TlsIdentity {
+            acceptor: TlsAcceptor::from(Arc::new(config)),
+            ca_certificate,
+        })
+    }
+}
+
+impl ClientAuth {
+    fn verifier(&self) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>, String> {
+        let path = &self.trusted_certificates;
+        let mut roots = RootCertStore::empty();
+        for certificate in CertificateDer::pem_file_iter(path).map_err(|e| {
+            format!(
+                "Cannot read trusted certificates {} : {}",
+                path.display(),
+                e
+            )
+        })? {
+            let certificate = certificate.map_err(|e| {
+                format!(
+                    "Cannot read trusted certificates {} : {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            roots.add(certificate).map_err(|e| {
+                format!("Invalid trusted certificate in {} : {}", path.display(), e)
+            })?;
+        }
+
+        let builder = WebPkiClientVerifier::builder(Arc::new(roots));
+        let builder = if self.required {
+            builder
+        } else {
+            builder.allow_unauthenticated()
+        };
+        builder
+            .build()
+            .map_err(|e| format!("Cannot verify client certificates : {}", e))
+    }
+}
+
+type GeneratedCertificate = (Vec<CertificateDer<'static>>, PrivateKeyDer<'static>, String);
+
+fn self_signed(subject_alt_names: &[String]) -> Result<GeneratedCertificate, rcgen::Error> {
+    let mut ca_params = CertificateParams::new(Vec::<String>::new())?;
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, format!("{} CA", env!("CARGO_PKG_NAME")));
+    ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate()?)?;
+
+    let mut params = CertificateParams::new(subject_alt_names.to_vec())?;
+    params.distinguished_name.push(
+        DnType::CommonName,
+        subject_alt_names
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "localhost".to_string()),
+    );
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+    let key = KeyPair::generate()?;
+    let certificate = params.signed_by(&key, &ca)?;
+
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der()));
+    Ok((
+        vec![certificate.der().clone(), ca.der().clone()],
+        key,
+        ca.pem(),
+    ))
+}
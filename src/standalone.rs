@@ -1,9 +1,25 @@
+use crate::core::registry::StubRegistry;
+use crate::server::config::{ServerConfig, MAPPINGS_DIR};
+use crate::server::mappings::{load_file_mappings, reload_on_change, reload_on_hangup};
 use crate::server::start_server;
 use std::future::Future;
+use std::sync::Arc;
 
-pub async fn start_standalone_server<F>(port: u16, expose: bool, shutdown: F) -> Result<(), String>
+pub async fn start_standalone_server<F>(config: ServerConfig, shutdown: F) -> Result<(), String>
 where
     F: Future<Output = ()> + Send + 'static,
 {
-    start_server(port, expose, shutdown).await
+    let registry = Arc::new(StubRegistry::new());
+
+    let dir = config.root_dir.join(MAPPINGS_DIR);
+    if let Some(summary) = load_file_mappings(&dir, &registry) {
+        log::info!("Loaded mappings from {} : {}", dir.display(), summary);
+    }
+
+    reload_on_hangup(config.root_dir.clone(), Arc::clone(&registry));
+    if config.watch_files {
+        reload_on_change(config.root_dir.clone(), Arc::clone(&registry))?;
+    }
+
+    start_server(config, registry, shutdown).await
 }